@@ -0,0 +1,65 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use bevy_progress_tracking::{Progress, ProgressCount, Task};
+
+struct Loading;
+
+fn track(c: &mut Criterion) {
+    c.bench_function("track 10k tasks", |b| {
+        let mut progress = Progress::<Loading>::default();
+        b.iter(|| {
+            for index in 0..10_000 {
+                progress.task(if index % 2 == 0 {
+                    Task::Done
+                } else {
+                    Task::InProgress
+                });
+            }
+            progress.finish_frame();
+        })
+    });
+}
+
+fn finish_frame(c: &mut Criterion) {
+    c.bench_function("finish frame with 10k named tasks", |b| {
+        let mut progress = Progress::<Loading>::default();
+        for _ in 0..10_000 {
+            let task = progress.add_named_task("chunk");
+            progress.complete_named_task(task);
+        }
+        progress.persist_done_tasks(42);
+        b.iter(|| {
+            progress.finish_frame();
+            black_box(progress.progress())
+        })
+    });
+}
+
+fn named_tasks(c: &mut Criterion) {
+    c.bench_function("add and remove 10k named tasks", |b| {
+        let mut progress = Progress::<Loading>::default();
+        let mut tasks = Vec::with_capacity(10_000);
+        b.iter(|| {
+            tasks.extend((0..10_000).map(|_| progress.add_named_task("chunk")));
+            for task in tasks.drain(..) {
+                progress.remove_named_task(task);
+            }
+        })
+    });
+}
+
+fn aggregate(c: &mut Criterion) {
+    let counts: Vec<ProgressCount> = (0..10_000)
+        .map(|index| ProgressCount::new(index % 3, 3))
+        .collect();
+    let loaded: Vec<bool> = (0..10_000).map(|index| index % 2 == 0).collect();
+    c.bench_function("sum 10k counts", |b| {
+        b.iter(|| black_box(counts.iter().sum::<ProgressCount>()))
+    });
+    c.bench_function("collect 10k bools", |b| {
+        b.iter(|| black_box(loaded.iter().copied().collect::<ProgressCount>()))
+    });
+}
+
+criterion_group!(benches, track, finish_frame, named_tasks, aggregate);
+criterion_main!(benches);
@@ -0,0 +1,1162 @@
+use std::time::Duration;
+
+use bevy::app::{App, AppLabel, First, Plugin, PostUpdate, Update};
+#[cfg(feature = "assets")]
+use bevy::asset::{Asset, AssetEvent, AssetEvents, AssetLoadFailedEvent, AssetServer, Assets};
+#[cfg(feature = "audio")]
+use bevy::audio::{AudioSink, SpatialAudioSink};
+use bevy::ecs::bundle::Bundle;
+use bevy::ecs::component::Component;
+use bevy::ecs::event::Event;
+use bevy::ecs::query::{QueryData, QueryFilter};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::common_conditions::resource_exists;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::IntoSystem;
+use bevy::ecs::world::World;
+#[cfg(feature = "asset_loader")]
+use bevy_asset_loader::dynamic_asset::DynamicAssets;
+
+#[cfg(feature = "assets")]
+use crate::assets::{poll_tracked_assets, resolve_asset_events};
+#[cfg(feature = "atlas")]
+use crate::atlas::build_texture_atlases;
+#[cfg(feature = "audio")]
+use crate::audio::crossfade_audio;
+use crate::bounded::{track_bounded_components, track_bounded_resource};
+use crate::checkpoint::send_checkpoints;
+use crate::cleanup::spawn_on_complete;
+use crate::component::{aggregate_components, FinishComponentFrames};
+use crate::conditions::{
+    complete_on_event_with, complete_on_removed, track_query_done, track_resource_with, track_until,
+};
+#[cfg(feature = "asset_loader")]
+use crate::dynamic_assets::track_dynamic_assets;
+use crate::forward::{continue_in_background, forward};
+#[cfg(feature = "gpu")]
+use crate::gpu::{complete_gpu_task, remove_gpu_task, start_gpu_task};
+use crate::jobs::track_timed_jobs;
+#[cfg(feature = "kira_audio")]
+use crate::kira_audio::crossfade_channels;
+use crate::objectives::{
+    advance_on_added, advance_on_event, report_objectives, ObjectivesReported,
+};
+#[cfg(feature = "assets")]
+use crate::packages::{fall_back_when_slow, track_packages};
+use crate::plugin::with_tracker_schedules;
+use crate::pressure::send_pressure_events;
+use crate::provider::track_provider;
+#[cfg(feature = "assets")]
+use crate::reload::track_reloads;
+use crate::shared::mirror_shared_progress;
+use crate::startup::add_timed_plugin;
+use crate::thresholds::run_at_progress;
+use crate::tips::rotate_tips;
+use crate::warm_up::{defer_completion_until_stable, track_warm_up};
+use crate::worlds::publish_world_progress;
+#[cfg(feature = "atlas")]
+use crate::TextureAtlasJobs;
+#[cfg(feature = "assets")]
+use crate::{AssetReload, PackageManifest, Progress, TrackedAssets};
+use crate::{
+    BoundedValueProgress, CheckpointJob, LoadingTips, ObjectiveCompleted, Objectives,
+    ProgressCheckpoint, ProgressMarker, ProgressPressure, ProgressProvider, ProgressSystems,
+    SharedProgress, TimedJobCompleted, WarmUp, WorldsProgress,
+};
+
+/// When a task tracked with [`ProgressAppExt::track_query_done`] is done
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QueryCompletion {
+    /// The task is done once the query matches at least one entity
+    AnyMatch,
+    /// The task is done once the query does not match any entity
+    NoMatch,
+}
+
+/// Extension trait to declare tracked tasks while building the [`App`]
+///
+/// All trackers used here need to be added to the app with a [`ProgressPlugin`](crate::ProgressPlugin).
+pub trait ProgressAppExt {
+    /// Track a single task that is done as soon as the given condition returns `true`
+    ///
+    /// The condition is polled once per frame in `Update` until it returns `true` for the first time.
+    /// From then on, the task is reported as done without calling the condition again.
+    /// Conditions only get shared access to the world and report through [`SharedProgress<T>`],
+    /// so they run in parallel with other systems that do not write to the world.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// # #[derive(Resource)]
+    /// # struct Map;
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .track_until::<Loading, _>(|world| world.contains_resource::<Map>());
+    /// ```
+    fn track_until<T, F>(&mut self, condition: F) -> &mut Self
+    where
+        T: ProgressMarker,
+        F: FnMut(&World) -> bool + Send + Sync + 'static;
+
+    /// Track a single task that is done as soon as the query `Q` with the filter `F` fulfills the given completion
+    ///
+    /// Like [`ProgressAppExt::track_until`], the task stays done once the completion was fulfilled.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin, QueryCompletion};
+    /// # struct Loading;
+    /// #[derive(Component)]
+    /// struct NeedsMesh;
+    ///
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .track_query_done::<Loading, Entity, With<NeedsMesh>>(QueryCompletion::NoMatch);
+    /// ```
+    fn track_query_done<T, Q, F>(&mut self, completion: QueryCompletion) -> &mut Self
+    where
+        T: ProgressMarker,
+        Q: QueryData + 'static,
+        F: QueryFilter + 'static;
+
+    /// Track a single task that is done as soon as the resource `R` exists
+    ///
+    /// This can be used to wait for the initialization of other plugins.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// # #[derive(Resource)]
+    /// # struct AudioDevice;
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .track_resource::<Loading, AudioDevice>();
+    /// ```
+    fn track_resource<T, R>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        R: Resource;
+
+    /// Track a single task that is done as soon as the resource `R` exists and the predicate returns `true` for it
+    ///
+    /// Like [`ProgressAppExt::track_until`], the task stays done once the predicate returned `true`.
+    fn track_resource_with<T, R, P>(&mut self, predicate: P) -> &mut Self
+    where
+        T: ProgressMarker,
+        R: Resource,
+        P: FnMut(&R) -> bool + Send + Sync + 'static;
+
+    /// Track a single task that is done as soon as an event of type `E` is sent
+    ///
+    /// The event type is added to the app if it was not added before.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// #[derive(Event)]
+    /// struct PhysicsReady;
+    ///
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .complete_on_event::<Loading, PhysicsReady>();
+    /// ```
+    fn complete_on_event<T, E>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        E: Event;
+
+    /// Track a single task that is done as soon as an event of type `E` is sent for which the predicate returns `true`
+    fn complete_on_event_with<T, E, P>(&mut self, predicate: P) -> &mut Self
+    where
+        T: ProgressMarker,
+        E: Event,
+        P: FnMut(&E) -> bool + Send + Sync + 'static;
+
+    /// Mirror the progress of the tracker `Source` into the tracker `Target` as `weight` tasks
+    ///
+    /// The done tasks are the share of `weight` corresponding to the progress of `Source`, rounded down.
+    /// This allows plugins to track their own progress, while the app rolls it up into a global tracker.
+    /// Since the last finished frame of `Source` is forwarded, `Target` lags one frame behind.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// struct MapGeneration;
+    /// struct Loading;
+    ///
+    /// App::new()
+    ///     .add_plugins((
+    ///         ProgressPlugin::<MapGeneration>::new(),
+    ///         ProgressPlugin::<Loading>::new(),
+    ///     ))
+    ///     .forward::<MapGeneration, Loading>(10);
+    /// ```
+    fn forward<Source, Target>(&mut self, weight: usize) -> &mut Self
+    where
+        Source: ProgressMarker,
+        Target: ProgressMarker;
+
+    /// Track the soft tasks of the tracker `T` on the tracker `Background` once `T` is ready
+    ///
+    /// Once `T` tracked tasks and all of its hard tasks are done, its [soft tasks](crate::Progress::track_soft) that were
+    /// still outstanding at that point keep being reported to `Background` every frame. This can drive an in-game
+    /// indicator for assets that are still streaming after the loading screen is gone. Reporting stops
+    /// once `T` is not ready anymore, for example because a new loading session started.
+    /// Like [`ProgressAppExt::forward`], `Background` lags one frame behind `T`.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// struct Loading;
+    /// struct Background;
+    ///
+    /// App::new()
+    ///     .add_plugins((
+    ///         ProgressPlugin::<Loading>::new(),
+    ///         ProgressPlugin::<Background>::new(),
+    ///     ))
+    ///     .continue_in_background::<Loading, Background>();
+    /// ```
+    fn continue_in_background<T, Background>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        Background: ProgressMarker;
+
+    /// Report the progress of the given provider to the tracker `T` every frame
+    fn register_progress_provider<T, P>(&mut self, provider: P) -> &mut Self
+    where
+        T: ProgressMarker,
+        P: ProgressProvider;
+
+    /// Track the given warm-up step as a hidden task of the tracker `T`
+    ///
+    /// The step is polled once per frame in `Update` until it is warm. See [`WarmUp`].
+    fn register_warm_up<T, W>(&mut self, warm_up: W) -> &mut Self
+    where
+        T: ProgressMarker,
+        W: WarmUp;
+
+    /// Resolve tracked assets of type `A` in [`TrackedAssets<T>`] through asset events instead of polling them
+    ///
+    /// Every tracked asset of this type is only polled once. If it is not loaded at that point,
+    /// the tracker waits for its [`AssetEvent::LoadedWithDependencies`] or [`AssetLoadFailedEvent`].
+    /// This reduces the work per frame from the number of tracked assets to the number of asset events.
+    /// Events are resolved in `Update`, one frame after they were sent, unless the plugin was added
+    /// [with strict same frame](crate::ProgressPlugin::with_strict_same_frame).
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// # #[derive(Asset, TypePath)]
+    /// # struct Chunk;
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .track_asset_events::<Loading, Chunk>();
+    /// ```
+    #[cfg(feature = "assets")]
+    fn track_asset_events<T, A>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        A: Asset;
+
+    /// Track reloads of loaded assets of type `A` as a background session of the tracker `T`
+    ///
+    /// While assets are reloading, for example through asset hot reloading, they are reported as the
+    /// source `"reloads"` in `Update`, and an [`AssetReload<T>`](crate::AssetReload) event is sent when
+    /// a reload starts and when it finishes. The session is closed after all reloads are finished.
+    /// All assets of the type are checked every frame, so this is meant for development builds and editors.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # #[derive(Asset, TypePath)]
+    /// # struct Level;
+    /// struct Reloading;
+    ///
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Reloading>::new())
+    ///     .track_reloads::<Reloading, Level>();
+    /// ```
+    #[cfg(feature = "assets")]
+    fn track_reloads<T, A>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        A: Asset;
+
+    /// Mirror progress of the tracker `T` from the sub-app with the given label into the main world
+    ///
+    /// Systems in the sub-app report their progress to [`SharedProgress<T>`] in the sub-app world.
+    /// These counts are moved to the [`SharedProgress<T>`] of the main world during extraction.
+    /// This way, work in the render app can contribute to a loading bar in the main world.
+    ///
+    /// The counts of a sub-app frame are only moved during the extraction of the next frame,
+    /// so the main world tracker lags up to two frames behind the sub-app.
+    /// Call this after the sub-app set its own extract function, e.g. after adding the `RenderPlugin`.
+    fn mirror_sub_app_progress<T>(&mut self, label: impl AppLabel) -> &mut Self
+    where
+        T: ProgressMarker;
+
+    /// Track gameplay objectives in [`Objectives<T>`] as part of the tracker `T`
+    ///
+    /// The objectives are reported in `PostUpdate`, after gameplay systems in `Update` advanced them.
+    /// An [`ObjectiveCompleted<T>`] event is sent for every objective that reached its target.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{Objectives, ProgressAppExt, ProgressPlugin};
+    /// struct Quest;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins(ProgressPlugin::<Quest>::new())
+    ///     .track_objectives::<Quest>();
+    /// app.world_mut()
+    ///     .resource_mut::<Objectives<Quest>>()
+    ///     .define("collect gems", 10);
+    /// ```
+    fn track_objectives<T>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker;
+
+    /// Advance the objective with the given label by one for every event of type `E`
+    ///
+    /// Objectives are tracked with [`ProgressAppExt::track_objectives`] if that did not happen before.
+    /// The event type is added to the app if it was not added before.
+    fn advance_objective_on_event<T, E>(&mut self, label: &'static str) -> &mut Self
+    where
+        T: ProgressMarker,
+        E: Event;
+
+    /// Advance the objective with the given label by one for every component `C` added to an entity
+    ///
+    /// Objectives are tracked with [`ProgressAppExt::track_objectives`] if that did not happen before.
+    fn advance_objective_on_added<T, C>(&mut self, label: &'static str) -> &mut Self
+    where
+        T: ProgressMarker,
+        C: Component;
+
+    /// Track a named task for every entity with the component `C` that is done once the component is removed
+    ///
+    /// Tasks are added and completed in `Update`. Despawning the entity also completes its task.
+    /// Completed tasks are removed once the tracker is ready, so entities coming and going
+    /// over a long session do not pile up in the tracker.
+    /// This fits workflows that mark entities with a component like `Pending` while they are not ready.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// #[derive(Component)]
+    /// struct Pending;
+    ///
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .complete_on_removed::<Loading, Pending>();
+    /// ```
+    fn complete_on_removed<T, C>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        C: Component;
+
+    /// Track all [`TimedJob<T>`](crate::TimedJob) components as part of the tracker `T`
+    ///
+    /// The jobs are reported in `Update` based on the elapsed [`Time`](bevy::time::Time),
+    /// which requires the `TimePlugin`. A [`TimedJobCompleted<T>`] event is sent for every job that is done.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// struct Crafting;
+    ///
+    /// App::new()
+    ///     .add_plugins((bevy::time::TimePlugin, ProgressPlugin::<Crafting>::new()))
+    ///     .track_timed_jobs::<Crafting>();
+    /// ```
+    fn track_timed_jobs<T>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker;
+
+    /// Track the resource `R` as a bounded value of the tracker `T`
+    ///
+    /// The value is reported as 1000 tasks in `Update` while the resource exists.
+    /// See [`BoundedValueProgress`].
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{BoundedValueProgress, ProgressAppExt, ProgressPlugin};
+    /// struct Experience;
+    ///
+    /// #[derive(Resource)]
+    /// struct PlayerLevel {
+    ///     points: u32,
+    ///     next_level: u32,
+    /// }
+    ///
+    /// impl BoundedValueProgress for PlayerLevel {
+    ///     fn current(&self) -> f32 {
+    ///         self.points as f32
+    ///     }
+    ///
+    ///     fn max(&self) -> f32 {
+    ///         self.next_level as f32
+    ///     }
+    /// }
+    ///
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Experience>::new())
+    ///     .track_bounded_resource::<Experience, PlayerLevel>();
+    /// ```
+    fn track_bounded_resource<T, R>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        R: Resource + BoundedValueProgress;
+
+    /// Track the job `J` as part of the tracker `T` and send a [`ProgressCheckpoint<T>`] every `interval` of its progress
+    ///
+    /// With an interval of `0.1`, a checkpoint is sent every 10%. The job is reported and checkpoints are sent
+    /// in `PostUpdate`, after the job worked in `Update`. See [`CheckpointJob`].
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{CheckpointJob, ProgressAppExt, ProgressCount, ProgressPlugin};
+    /// # #[derive(Resource)]
+    /// # struct BakeLightmaps;
+    /// # impl CheckpointJob for BakeLightmaps {
+    /// #     fn count(&self) -> ProgressCount { ProgressCount::pending(1) }
+    /// #     fn cursor(&self) -> String { String::new() }
+    /// # }
+    /// struct Baking;
+    ///
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Baking>::new())
+    ///     .track_checkpointed_job::<Baking, BakeLightmaps>(0.05);
+    /// ```
+    fn track_checkpointed_job<T, J>(&mut self, interval: f32) -> &mut Self
+    where
+        T: ProgressMarker,
+        J: CheckpointJob;
+
+    /// Track every component `C` as a bounded value of the tracker `T`
+    ///
+    /// Every component is reported as 1000 tasks in `Update`. See [`BoundedValueProgress`].
+    fn track_bounded_components<T, C>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        C: Component + BoundedValueProgress;
+
+    /// Sum up all [`ProgressComponent<T>`](crate::ProgressComponent)s matching the filter `F` as progress of the tracker `T`
+    ///
+    /// The components are added up right before the tracker finishes its frame, after their frames were finished
+    /// by the [`ProgressComponentPlugin`](crate::ProgressComponentPlugin), so the tracker includes them in the frame they were tracked in.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressComponentPlugin, ProgressPlugin};
+    /// # struct Construction;
+    /// #[derive(Component)]
+    /// struct PlayerBase;
+    ///
+    /// App::new()
+    ///     .add_plugins((
+    ///         ProgressPlugin::<Construction>::new(),
+    ///         ProgressComponentPlugin::<Construction>::new(),
+    ///     ))
+    ///     .aggregate_components::<Construction, With<PlayerBase>>();
+    /// ```
+    fn aggregate_components<T, F>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        F: QueryFilter + 'static;
+
+    /// Load and track the packages of the [`PackageManifest<T>`] as part of the tracker `T`
+    ///
+    /// New assets of the manifest are loaded and all packages are reported in `Update`,
+    /// if the app has an [`AssetServer`](bevy::asset::AssetServer). An empty manifest is added if there is none.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{PackageManifest, ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// let manifest = PackageManifest::<Loading>::parse("[base]\nlevels/forest.ron").unwrap();
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .insert_resource(manifest)
+    ///     .track_packages::<Loading>();
+    /// ```
+    #[cfg(feature = "assets")]
+    fn track_packages<T>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker;
+
+    /// Track the assets of [`DynamicAssets`](bevy_asset_loader::dynamic_asset::DynamicAssets) in [`TrackedAssets<T>`]
+    ///
+    /// Whenever a dynamic asset collection of `bevy_asset_loader` registers new keys, the assets of every key are
+    /// tracked individually, instead of the whole collection counting as one task. Keys are checked in `Update`.
+    /// Only available with the `asset_loader` feature.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .track_dynamic_assets::<Loading>();
+    /// ```
+    #[cfg(feature = "asset_loader")]
+    fn track_dynamic_assets<T>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker;
+
+    /// Build the texture atlases queued in [`TextureAtlasJobs<T>`] as tasks of the tracker `T`
+    ///
+    /// The resource is initialized here, so systems can queue atlases from now on. See [`TextureAtlasJobs`].
+    /// Only available with the `atlas` feature.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .build_texture_atlases::<Loading>();
+    /// ```
+    #[cfg(feature = "atlas")]
+    fn build_texture_atlases<T>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker;
+
+    /// Track every [`GpuTask<T>`](crate::GpuTask) as a named task of the tracker `T`
+    ///
+    /// Tasks are added when the component is added and completed by observers of their
+    /// [`ReadbackComplete`](bevy::render::gpu_readback::ReadbackComplete) events. Only available with the `gpu` feature.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .track_gpu_tasks::<Loading>();
+    /// ```
+    #[cfg(feature = "gpu")]
+    fn track_gpu_tasks<T>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker;
+
+    /// Fade the audio of every entity with a [`ProgressCrossfade<T>`](crate::ProgressCrossfade) across the completion of the tracker `T`
+    ///
+    /// The volumes of `AudioSink`s and `SpatialAudioSink`s are faded in `Update`. Only available with the `audio` feature.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .crossfade_audio::<Loading>();
+    /// ```
+    #[cfg(feature = "audio")]
+    fn crossfade_audio<T>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker;
+
+    /// Crossfade between two audio channels of `bevy_kira_audio` across the completion of the tracker `T`
+    ///
+    /// Once the tracker tracked tasks and [is ready](crate::Progress::is_ready), the loading screen channel `L`
+    /// is paused while fading out over `fade_out` and the gameplay channel `G` is resumed while fading in over `fade_in`.
+    /// The channels swap the other way once the tracker starts tracking tasks that are not done,
+    /// which also pauses the gameplay channel when the first loading starts.
+    /// Both channels need to be added with `add_audio_channel`. Only available with the `kira_audio` feature.
+    /// ```edition2021
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::*;
+    /// # use bevy_kira_audio::{AudioApp, AudioPlugin};
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// #[derive(Resource)]
+    /// struct LoadingMusic;
+    /// #[derive(Resource)]
+    /// struct GameplayMusic;
+    ///
+    /// App::new()
+    ///     .add_plugins((AssetPlugin::default(), AudioPlugin, ProgressPlugin::<Loading>::new()))
+    ///     .add_audio_channel::<LoadingMusic>()
+    ///     .add_audio_channel::<GameplayMusic>()
+    ///     .crossfade_kira_channels::<Loading, LoadingMusic, GameplayMusic>(
+    ///         Duration::from_secs(2),
+    ///         Duration::from_secs(4),
+    ///     );
+    /// ```
+    #[cfg(feature = "kira_audio")]
+    fn crossfade_kira_channels<T, L, G>(
+        &mut self,
+        fade_out: Duration,
+        fade_in: Duration,
+    ) -> &mut Self
+    where
+        T: ProgressMarker,
+        L: Resource,
+        G: Resource;
+
+    /// Rotate through the given tips while the tracker `T` is not complete
+    ///
+    /// The tips are inserted as resource and rotated in `Update`. See [`LoadingTips`].
+    fn rotate_tips<T>(&mut self, tips: LoadingTips<T>) -> &mut Self
+    where
+        T: ProgressMarker;
+
+    /// Defer the readiness of the tracker `T` until frame times are stable
+    ///
+    /// Once all other tasks are done, the frame time has to stay below `max_frame_time` for `frames`
+    /// consecutive frames, giving shader compilation and clean-up of the loading time to settle before the game starts.
+    /// Whenever the other hard or hidden tasks change, for example a warm-up that is pending again, the frames have to be stable again.
+    /// This is tracked as a [hidden task](crate::Progress::track_hidden), so gate the transition on
+    /// [`Progress::is_ready`](crate::Progress::is_ready). Frame times are taken from the real [`Time`](bevy::time::Time), which requires the `TimePlugin`.
+    /// ```edition2021
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// App::new()
+    ///     .add_plugins((bevy::time::TimePlugin, ProgressPlugin::<Loading>::new()))
+    ///     .defer_completion_until_stable::<Loading>(Duration::from_millis(20), 30);
+    /// ```
+    fn defer_completion_until_stable<T>(
+        &mut self,
+        max_frame_time: Duration,
+        frames: u32,
+    ) -> &mut Self
+    where
+        T: ProgressMarker;
+
+    /// Call the fallback once if loading the tracker `T` is estimated to take longer than `max_eta`
+    ///
+    /// The [estimate](crate::Progress::eta) is made once, after the tracker was [loading](crate::Progress::loading_time)
+    /// for `check_after`. Without any progress at that point, loading counts as slow.
+    /// The fallback gets the tracker with the outstanding [named tasks](crate::Progress::named_tasks) and
+    /// [sources](crate::Progress::contributions), and can replace or cancel queued assets of the
+    /// [`PackageManifest<T>`] with [`PackageManifest::retain_queued`], for example to load lower quality
+    /// textures on slow machines. It runs in `Update` before new package assets are requested.
+    /// ```edition2021
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .track_packages::<Loading>()
+    ///     .fall_back_when_slow::<Loading, _>(
+    ///         Duration::from_secs(3),
+    ///         Duration::from_secs(30),
+    ///         |progress, manifest| {
+    ///             for (_, label, done) in progress.named_tasks() {
+    ///                 if !done {
+    ///                     warn!("Still waiting for {label}");
+    ///                 }
+    ///             }
+    ///             manifest.retain_queued(|package, _| package != "hd textures");
+    ///         },
+    ///     );
+    /// ```
+    #[cfg(feature = "assets")]
+    fn fall_back_when_slow<T, F>(
+        &mut self,
+        check_after: Duration,
+        max_eta: Duration,
+        fallback: F,
+    ) -> &mut Self
+    where
+        T: ProgressMarker,
+        F: FnMut(&Progress<T>, &mut PackageManifest<T>) + Send + Sync + 'static;
+
+    /// Publish the progress of the tracker `T` to the given [`WorldsProgress`] every frame
+    ///
+    /// Use clones of the same [`WorldsProgress`] in multiple apps to aggregate their progress.
+    /// The counts of the last finished frame are published at the beginning of every frame in [`First`].
+    fn share_progress_across_worlds<T>(&mut self, worlds: WorldsProgress<T>) -> &mut Self
+    where
+        T: ProgressMarker;
+
+    /// Spawn the bundle returned by the factory once the tracker `T` completes
+    ///
+    /// The bundle is spawned exactly once, right after finishing the first frame the tracker had tasks and
+    /// [is ready](crate::Progress::is_ready) in, for example to spawn the player right as loading ends.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// # #[derive(Component)]
+    /// # struct Player;
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .spawn_on_complete::<Loading, _>(|| (Name::new("Player"), Player));
+    /// ```
+    fn spawn_on_complete<T, B>(
+        &mut self,
+        factory: impl FnOnce() -> B + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        T: ProgressMarker,
+        B: Bundle;
+
+    /// Run the one-shot system whenever the progress of the tracker `T` reaches the threshold
+    ///
+    /// The system runs right after finishing the frame the [progress](crate::Progress::progress) crossed the threshold
+    /// from below, and again in later sessions. This allows pipelining work with loading,
+    /// like decompressing audio at half of the progress and spawning the level at 90%.
+    /// Frames without tasks do not count.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin};
+    /// # struct Loading;
+    /// fn spawn_level(mut commands: Commands) {
+    ///     commands.spawn(Name::new("Level"));
+    /// }
+    ///
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .run_at_progress::<Loading, _>(0.9, spawn_level);
+    /// ```
+    fn run_at_progress<T, M>(
+        &mut self,
+        threshold: f32,
+        system: impl IntoSystem<(), (), M> + 'static,
+    ) -> &mut Self
+    where
+        T: ProgressMarker;
+
+    /// Send a [`ProgressPressure<T>`] event when the outstanding work of the source rises above the threshold
+    ///
+    /// The tasks of the source that are not done are checked every time the tracker finished a frame.
+    /// Another event is sent once the outstanding work falls back to the threshold or the source
+    /// stops reporting, so loaders can resume.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin, ProgressPressure};
+    /// # struct Loading;
+    /// fn throttle_decoding(mut pressure: EventReader<ProgressPressure<Loading>>) {
+    ///     for event in pressure.read() {
+    ///         info!("{} images waiting for upload, backing off: {}", event.outstanding, event.high);
+    ///     }
+    /// }
+    ///
+    /// App::new()
+    ///     .add_plugins(ProgressPlugin::<Loading>::new())
+    ///     .send_pressure_events::<Loading>("image uploads", 64)
+    ///     .add_systems(Update, throttle_decoding);
+    /// ```
+    fn send_pressure_events<T>(&mut self, source: &'static str, threshold: usize) -> &mut Self
+    where
+        T: ProgressMarker;
+
+    /// Add the plugin and track its build as a named task of the [`AppStartup`](crate::AppStartup) tracker
+    ///
+    /// The task is labeled with the [name](Plugin::name) of the plugin and done once the plugin and
+    /// the plugins it added are built. Their build time is listed in [`PluginTimings`](crate::PluginTimings).
+    /// The [`StartupProgressPlugin`](crate::StartupProgressPlugin) is added if it is missing.
+    /// ```edition2021
+    /// # use bevy::prelude::*;
+    /// # use bevy_progress_tracking::{ProgressAppExt, StartupProgressPlugin};
+    /// # struct WorldGenerationPlugin;
+    /// # impl Plugin for WorldGenerationPlugin {
+    /// #     fn build(&self, _app: &mut App) {}
+    /// # }
+    /// App::new()
+    ///     .add_plugins(StartupProgressPlugin)
+    ///     .add_timed_plugin(WorldGenerationPlugin);
+    /// ```
+    fn add_timed_plugin<P>(&mut self, plugin: P) -> &mut Self
+    where
+        P: Plugin;
+}
+
+impl ProgressAppExt for App {
+    fn track_until<T, F>(&mut self, condition: F) -> &mut Self
+    where
+        T: ProgressMarker,
+        F: FnMut(&World) -> bool + Send + Sync + 'static,
+    {
+        self.add_systems(Update, track_until::<T, F>(condition))
+    }
+
+    fn track_query_done<T, Q, F>(&mut self, completion: QueryCompletion) -> &mut Self
+    where
+        T: ProgressMarker,
+        Q: QueryData + 'static,
+        F: QueryFilter + 'static,
+    {
+        self.add_systems(Update, track_query_done::<T, Q, F>(completion))
+    }
+
+    fn track_resource<T, R>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        R: Resource,
+    {
+        self.track_resource_with::<T, R, _>(|_| true)
+    }
+
+    fn track_resource_with<T, R, P>(&mut self, predicate: P) -> &mut Self
+    where
+        T: ProgressMarker,
+        R: Resource,
+        P: FnMut(&R) -> bool + Send + Sync + 'static,
+    {
+        self.add_systems(Update, track_resource_with::<T, R, P>(predicate))
+    }
+
+    fn complete_on_event<T, E>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        E: Event,
+    {
+        self.complete_on_event_with::<T, E, _>(|_| true)
+    }
+
+    fn complete_on_event_with<T, E, P>(&mut self, predicate: P) -> &mut Self
+    where
+        T: ProgressMarker,
+        E: Event,
+        P: FnMut(&E) -> bool + Send + Sync + 'static,
+    {
+        self.add_event::<E>()
+            .add_systems(Update, complete_on_event_with::<T, E, P>(predicate))
+    }
+
+    fn forward<Source, Target>(&mut self, weight: usize) -> &mut Self
+    where
+        Source: ProgressMarker,
+        Target: ProgressMarker,
+    {
+        self.add_systems(Update, forward::<Source, Target>(weight))
+    }
+
+    fn continue_in_background<T, Background>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        Background: ProgressMarker,
+    {
+        self.add_systems(Update, continue_in_background::<T, Background>)
+    }
+
+    fn register_progress_provider<T, P>(&mut self, provider: P) -> &mut Self
+    where
+        T: ProgressMarker,
+        P: ProgressProvider,
+    {
+        self.add_systems(Update, track_provider::<T, P>(provider))
+    }
+
+    fn register_warm_up<T, W>(&mut self, warm_up: W) -> &mut Self
+    where
+        T: ProgressMarker,
+        W: WarmUp,
+    {
+        self.add_systems(Update, track_warm_up::<T, W>(warm_up))
+    }
+
+    #[cfg(feature = "assets")]
+    fn track_asset_events<T, A>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        A: Asset,
+    {
+        self.world_mut()
+            .get_resource_or_init::<TrackedAssets<T>>()
+            .resolve_through_events::<A>();
+        self.add_event::<AssetEvent<A>>()
+            .add_event::<AssetLoadFailedEvent<A>>();
+        with_tracker_schedules::<T>(self, |app, schedules| {
+            let resolve = resolve_asset_events::<T, A>.before(poll_tracked_assets::<T>);
+            if schedules.strict_same_frame {
+                app.add_systems(PostUpdate, resolve.after(AssetEvents));
+            } else {
+                app.add_systems(Update, resolve);
+            }
+        });
+        self
+    }
+
+    #[cfg(feature = "assets")]
+    fn track_reloads<T, A>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        A: Asset,
+    {
+        self.add_event::<AssetReload<T>>().add_systems(
+            Update,
+            track_reloads::<T, A>
+                .run_if(resource_exists::<AssetServer>)
+                .run_if(resource_exists::<Assets<A>>),
+        )
+    }
+
+    fn mirror_sub_app_progress<T>(&mut self, label: impl AppLabel) -> &mut Self
+    where
+        T: ProgressMarker,
+    {
+        let sub_app = self.sub_app_mut(label);
+        sub_app.init_resource::<SharedProgress<T>>();
+        let extract = sub_app.take_extract();
+        sub_app.set_extract(move |main_world, sub_world| {
+            if let Some(extract) = extract.as_ref() {
+                extract(main_world, sub_world);
+            }
+            mirror_shared_progress::<T>(main_world, sub_world);
+        });
+        self
+    }
+
+    fn track_timed_jobs<T>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+    {
+        self.add_event::<TimedJobCompleted<T>>()
+            .add_systems(Update, track_timed_jobs::<T>)
+    }
+
+    fn track_bounded_resource<T, R>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        R: Resource + BoundedValueProgress,
+    {
+        self.add_systems(Update, track_bounded_resource::<T, R>)
+    }
+
+    fn track_checkpointed_job<T, J>(&mut self, interval: f32) -> &mut Self
+    where
+        T: ProgressMarker,
+        J: CheckpointJob,
+    {
+        self.add_event::<ProgressCheckpoint<T>>().add_systems(
+            PostUpdate,
+            send_checkpoints::<T, J>(interval).run_if(resource_exists::<J>),
+        )
+    }
+
+    fn track_bounded_components<T, C>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        C: Component + BoundedValueProgress,
+    {
+        self.add_systems(Update, track_bounded_components::<T, C>)
+    }
+
+    fn aggregate_components<T, F>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        F: QueryFilter + 'static,
+    {
+        with_tracker_schedules::<T>(self, |app, schedules| {
+            app.add_systems(
+                schedules.finish,
+                aggregate_components::<T, F>
+                    .after(FinishComponentFrames)
+                    .before(ProgressSystems::FinishFrame),
+            );
+        });
+        self
+    }
+
+    #[cfg(feature = "assets")]
+    fn track_packages<T>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+    {
+        self.world_mut()
+            .get_resource_or_init::<PackageManifest<T>>();
+        self.add_systems(
+            Update,
+            track_packages::<T>.run_if(resource_exists::<AssetServer>),
+        )
+    }
+
+    #[cfg(feature = "asset_loader")]
+    fn track_dynamic_assets<T>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+    {
+        self.world_mut().get_resource_or_init::<TrackedAssets<T>>();
+        self.add_systems(
+            Update,
+            track_dynamic_assets::<T>
+                .before(poll_tracked_assets::<T>)
+                .run_if(resource_exists::<AssetServer>)
+                .run_if(resource_exists::<DynamicAssets>),
+        )
+    }
+
+    #[cfg(feature = "atlas")]
+    fn build_texture_atlases<T>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+    {
+        self.init_resource::<TextureAtlasJobs<T>>()
+            .add_systems(Update, build_texture_atlases::<T>)
+    }
+
+    #[cfg(feature = "gpu")]
+    fn track_gpu_tasks<T>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+    {
+        self.add_observer(start_gpu_task::<T>)
+            .add_observer(complete_gpu_task::<T>)
+            .add_observer(remove_gpu_task::<T>)
+    }
+
+    #[cfg(feature = "audio")]
+    fn crossfade_audio<T>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+    {
+        self.add_systems(
+            Update,
+            (
+                crossfade_audio::<T, AudioSink>,
+                crossfade_audio::<T, SpatialAudioSink>,
+            ),
+        )
+    }
+
+    #[cfg(feature = "kira_audio")]
+    fn crossfade_kira_channels<T, L, G>(
+        &mut self,
+        fade_out: Duration,
+        fade_in: Duration,
+    ) -> &mut Self
+    where
+        T: ProgressMarker,
+        L: Resource,
+        G: Resource,
+    {
+        self.add_systems(Update, crossfade_channels::<T, L, G>(fade_out, fade_in))
+    }
+
+    fn rotate_tips<T>(&mut self, tips: LoadingTips<T>) -> &mut Self
+    where
+        T: ProgressMarker,
+    {
+        self.insert_resource(tips)
+            .add_systems(Update, rotate_tips::<T>)
+    }
+
+    fn defer_completion_until_stable<T>(
+        &mut self,
+        max_frame_time: Duration,
+        frames: u32,
+    ) -> &mut Self
+    where
+        T: ProgressMarker,
+    {
+        self.add_systems(
+            Update,
+            defer_completion_until_stable::<T>(max_frame_time, frames),
+        )
+    }
+
+    #[cfg(feature = "assets")]
+    fn fall_back_when_slow<T, F>(
+        &mut self,
+        check_after: Duration,
+        max_eta: Duration,
+        fallback: F,
+    ) -> &mut Self
+    where
+        T: ProgressMarker,
+        F: FnMut(&Progress<T>, &mut PackageManifest<T>) + Send + Sync + 'static,
+    {
+        self.add_systems(
+            Update,
+            fall_back_when_slow::<T, F>(check_after, max_eta, fallback).before(track_packages::<T>),
+        )
+    }
+
+    fn share_progress_across_worlds<T>(&mut self, worlds: WorldsProgress<T>) -> &mut Self
+    where
+        T: ProgressMarker,
+    {
+        self.insert_resource(worlds)
+            .add_systems(First, publish_world_progress::<T>)
+    }
+
+    fn spawn_on_complete<T, B>(
+        &mut self,
+        factory: impl FnOnce() -> B + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        T: ProgressMarker,
+        B: Bundle,
+    {
+        let system = spawn_on_complete::<T, B>(factory);
+        with_tracker_schedules::<T>(self, |app, schedules| {
+            app.add_systems(schedules.finish, system.after(ProgressSystems::FinishFrame));
+        });
+        self
+    }
+
+    fn run_at_progress<T, M>(
+        &mut self,
+        threshold: f32,
+        system: impl IntoSystem<(), (), M> + 'static,
+    ) -> &mut Self
+    where
+        T: ProgressMarker,
+    {
+        let system = self.register_system(system);
+        with_tracker_schedules::<T>(self, move |app, schedules| {
+            app.add_systems(
+                schedules.finish,
+                run_at_progress::<T>(threshold, system).after(ProgressSystems::FinishFrame),
+            );
+        });
+        self
+    }
+
+    fn send_pressure_events<T>(&mut self, source: &'static str, threshold: usize) -> &mut Self
+    where
+        T: ProgressMarker,
+    {
+        self.add_event::<ProgressPressure<T>>();
+        with_tracker_schedules::<T>(self, move |app, schedules| {
+            app.add_systems(
+                schedules.finish,
+                send_pressure_events::<T>(source, threshold).after(ProgressSystems::FinishFrame),
+            );
+        });
+        self
+    }
+
+    fn track_objectives<T>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+    {
+        self.init_resource::<Objectives<T>>();
+        if self.world().contains_resource::<ObjectivesReported<T>>() {
+            return self;
+        }
+        self.init_resource::<ObjectivesReported<T>>()
+            .add_event::<ObjectiveCompleted<T>>()
+            .add_systems(PostUpdate, report_objectives::<T>)
+    }
+
+    fn advance_objective_on_event<T, E>(&mut self, label: &'static str) -> &mut Self
+    where
+        T: ProgressMarker,
+        E: Event,
+    {
+        self.track_objectives::<T>()
+            .add_event::<E>()
+            .add_systems(Update, advance_on_event::<T, E>(label))
+    }
+
+    fn advance_objective_on_added<T, C>(&mut self, label: &'static str) -> &mut Self
+    where
+        T: ProgressMarker,
+        C: Component,
+    {
+        self.track_objectives::<T>()
+            .add_systems(Update, advance_on_added::<T, C>(label))
+    }
+
+    fn complete_on_removed<T, C>(&mut self) -> &mut Self
+    where
+        T: ProgressMarker,
+        C: Component,
+    {
+        self.add_systems(Update, complete_on_removed::<T, C>)
+    }
+
+    fn add_timed_plugin<P>(&mut self, plugin: P) -> &mut Self
+    where
+        P: Plugin,
+    {
+        add_timed_plugin(self, plugin);
+        self
+    }
+}
@@ -0,0 +1,381 @@
+use std::any::TypeId;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::asset::{
+    Asset, AssetEvent, AssetLoadFailedEvent, AssetServer, LoadState, RecursiveDependencyLoadState,
+    UntypedAssetId,
+};
+use bevy::ecs::event::EventReader;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Res, ResMut};
+use bevy::platform::collections::HashSet;
+use bevy::platform::time::Instant;
+
+#[cfg(feature = "perf")]
+use crate::ProgressPerf;
+use crate::{ProgressCount, ProgressMarker, SharedProgress};
+
+/// Resource keeping track of assets that are loading as part of the tracker `T`
+///
+/// Every tracked asset counts as one task that is done once the asset and all its dependencies are loaded.
+/// Assets that failed to load, or that have a dependency that failed to load, count as done as well,
+/// so a broken asset does not block the tracker. Handle failures through [`AssetLoadFailedEvent`].
+/// The [`ProgressPlugin`](crate::ProgressPlugin) polls the tracked assets every frame if an [`AssetServer`] exists.
+///
+/// Polling many assets every frame can get expensive. Assets of types registered with
+/// [`ProgressAppExt::track_asset_events`](crate::ProgressAppExt::track_asset_events) are only polled once
+/// and then wait for their [`AssetEvent::LoadedWithDependencies`] or [`AssetLoadFailedEvent`] instead.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::TrackedAssets;
+/// # struct Loading;
+/// # #[derive(Asset, TypePath)]
+/// # struct Level;
+/// #[derive(Resource)]
+/// struct LevelHandle(Handle<Level>);
+///
+/// fn load_level(
+///     mut commands: Commands,
+///     server: Res<AssetServer>,
+///     mut tracked: ResMut<TrackedAssets<Loading>>,
+/// ) {
+///     let handle = server.load("level.ron");
+///     tracked.track(&handle);
+///     commands.insert_resource(LevelHandle(handle));
+/// }
+/// ```
+#[derive(Resource)]
+pub struct TrackedAssets<T> {
+    pending: VecDeque<UntypedAssetId>,
+    awaiting_event: HashSet<UntypedAssetId>,
+    unloading: Vec<UntypedAssetId>,
+    event_types: HashSet<TypeId>,
+    done: usize,
+    budget: Option<Duration>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for TrackedAssets<T> {
+    fn default() -> Self {
+        TrackedAssets {
+            pending: VecDeque::new(),
+            awaiting_event: HashSet::default(),
+            unloading: Vec::new(),
+            event_types: HashSet::default(),
+            done: 0,
+            budget: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> TrackedAssets<T> {
+    /// Track the asset with the given id
+    ///
+    /// The tracker does not keep the asset alive. Store its handle for as long as you need the asset.
+    pub fn track(&mut self, id: impl Into<UntypedAssetId>) {
+        self.pending.push_back(id.into());
+    }
+
+    /// Track the unloading of the asset with the given id
+    ///
+    /// The task is done once the asset server freed the asset, which happens after all strong handles
+    /// to it were dropped. Use this to catch leaked handles during level transitions and to report honest
+    /// teardown progress. Assets that were not loaded through the [`AssetServer`] count as unloaded right away.
+    pub fn track_unload(&mut self, id: impl Into<UntypedAssetId>) {
+        self.unloading.push(id.into());
+    }
+
+    /// Limit the time spent polling tracked assets per frame
+    ///
+    /// Without a budget, all pending assets are polled every frame. With a budget, polling stops
+    /// once the budget is used up and continues with the remaining assets in the next frame.
+    /// At least one asset is polled every frame.
+    pub fn set_budget(&mut self, budget: Option<Duration>) {
+        self.budget = budget;
+    }
+
+    /// Returns the loaded or unloaded and total number of tracked assets
+    pub fn count(&self) -> ProgressCount {
+        ProgressCount::new(
+            self.done,
+            self.done + self.pending.len() + self.awaiting_event.len() + self.unloading.len(),
+        )
+    }
+
+    /// Stop tracking all assets
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.awaiting_event.clear();
+        self.unloading.clear();
+        self.done = 0;
+    }
+
+    pub(crate) fn resolve_through_events<A: Asset>(&mut self) {
+        self.event_types.insert(TypeId::of::<A>());
+    }
+
+    fn finished(&mut self, id: UntypedAssetId) {
+        if self.awaiting_event.remove(&id) {
+            self.done += 1;
+        }
+    }
+
+    fn poll(&mut self, mut is_finished: impl FnMut(UntypedAssetId) -> bool) {
+        let start = Instant::now();
+        for polled in 0..self.pending.len() {
+            if polled > 0 && self.budget.is_some_and(|budget| start.elapsed() >= budget) {
+                break;
+            }
+            let Some(id) = self.pending.pop_front() else {
+                break;
+            };
+            if is_finished(id) {
+                self.done += 1;
+            } else if self.event_types.contains(&id.type_id()) {
+                self.awaiting_event.insert(id);
+            } else {
+                self.pending.push_back(id);
+            }
+        }
+    }
+
+    fn poll_unloads(&mut self, mut is_unloaded: impl FnMut(UntypedAssetId) -> bool) {
+        let before = self.unloading.len();
+        self.unloading.retain(|id| !is_unloaded(*id));
+        self.done += before - self.unloading.len();
+    }
+}
+
+/// Load state of an asset together with all its dependencies
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AssetLoad {
+    Loading,
+    Loaded,
+    Failed,
+}
+
+/// Returns the load state of the asset and all its dependencies
+///
+/// The asset failed if it or any of its dependencies failed to load.
+pub(crate) fn asset_load(server: &AssetServer, id: UntypedAssetId) -> AssetLoad {
+    if server.is_loaded_with_dependencies(id) {
+        AssetLoad::Loaded
+    } else if matches!(server.get_load_state(id), Some(LoadState::Failed(_)))
+        || matches!(
+            server.get_recursive_dependency_load_state(id),
+            Some(RecursiveDependencyLoadState::Failed(_))
+        )
+    {
+        AssetLoad::Failed
+    } else {
+        AssetLoad::Loading
+    }
+}
+
+/// Returns `true` if the asset and all its dependencies are loaded or any of them failed to load
+pub(crate) fn is_finished(server: &AssetServer, id: UntypedAssetId) -> bool {
+    asset_load(server, id) != AssetLoad::Loading
+}
+
+pub(crate) fn poll_tracked_assets<T: ProgressMarker>(
+    mut assets: ResMut<TrackedAssets<T>>,
+    server: Res<AssetServer>,
+    progress: Res<SharedProgress<T>>,
+    #[cfg(feature = "perf")] mut perf: ResMut<ProgressPerf<T>>,
+) {
+    #[cfg(feature = "perf")]
+    let start = Instant::now();
+    assets.poll(|id| is_finished(&server, id));
+    assets.poll_unloads(|id| server.get_load_state(id).is_none());
+    progress.track_count(assets.count());
+    #[cfg(feature = "perf")]
+    perf.record_asset_polling(start.elapsed());
+}
+
+pub(crate) fn resolve_asset_events<T: ProgressMarker, A: Asset>(
+    mut assets: ResMut<TrackedAssets<T>>,
+    mut events: EventReader<AssetEvent<A>>,
+    mut failures: EventReader<AssetLoadFailedEvent<A>>,
+) {
+    for event in events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } = event {
+            assets.finished(id.untyped());
+        }
+    }
+    for failure in failures.read() {
+        assets.finished(failure.id.untyped());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::app::{App, PostUpdate};
+    use bevy::asset::io::AssetSourceBuilders;
+    use bevy::asset::{
+        Asset, AssetEvent, AssetEvents, AssetId, AssetIndex, AssetLoadError, AssetLoadFailedEvent,
+        AssetPath, AssetPlugin, AssetServer, AssetServerMode, UnapprovedPathMode, UntypedAssetId,
+    };
+    use bevy::ecs::event::EventWriter;
+    use bevy::ecs::schedule::IntoScheduleConfigs;
+    use bevy::ecs::system::Local;
+    use bevy::reflect::TypePath;
+
+    use crate::{Progress, ProgressAppExt, ProgressCount, ProgressPlugin, TrackedAssets};
+
+    struct Loading;
+
+    #[derive(Asset, TypePath)]
+    struct Level;
+
+    #[test]
+    fn unloads_are_done_once_freed() {
+        let ids: Vec<UntypedAssetId> = (0..2)
+            .map(|index| AssetId::<Level>::from(AssetIndex::from_bits(index)).untyped())
+            .collect();
+        let mut assets = TrackedAssets::<Loading>::default();
+        assets.track(ids[0]);
+        assets.track_unload(ids[1]);
+
+        assets.poll_unloads(|_| false);
+        assert_eq!(assets.count(), ProgressCount::pending(2));
+        assets.poll_unloads(|id| id == ids[1]);
+        assert_eq!(assets.count(), ProgressCount::new(1, 2));
+    }
+
+    #[test]
+    fn budget_continues_polling_next_frame() {
+        let mut assets = TrackedAssets::<Loading>::default();
+        assets.set_budget(Some(Duration::ZERO));
+        for _ in 0..3 {
+            assets.track(AssetId::<Level>::invalid());
+        }
+
+        let polled = |assets: &mut TrackedAssets<Loading>| {
+            let mut polled = 0;
+            assets.poll(|_: UntypedAssetId| {
+                polled += 1;
+                true
+            });
+            polled
+        };
+        assert_eq!(polled(&mut assets), 1);
+        assert_eq!(assets.count(), ProgressCount::new(1, 3));
+
+        assets.set_budget(None);
+        assert_eq!(polled(&mut assets), 2);
+        assert!(assets.count().is_complete());
+    }
+
+    #[test]
+    fn event_types_are_polled_once() {
+        let mut assets = TrackedAssets::<Loading>::default();
+        assets.resolve_through_events::<Level>();
+        let id = AssetId::<Level>::invalid().untyped();
+        assets.track(id);
+
+        assets.poll(|_| false);
+        assets.poll(|_| panic!("Assets awaiting events should not be polled"));
+        assert_eq!(assets.count(), ProgressCount::pending(1));
+
+        assets.finished(id);
+        assets.finished(id);
+        assert_eq!(assets.count(), ProgressCount::done(1));
+    }
+
+    fn app_tracking_events(plugin: ProgressPlugin<Loading>) -> App {
+        let mut sources = AssetSourceBuilders::default();
+        sources.init_default_source("assets", None);
+        let server = AssetServer::new(
+            sources.build_sources(false, false),
+            AssetServerMode::Unprocessed,
+            false,
+            UnapprovedPathMode::Forbid,
+        );
+        let mut app = App::new();
+        // Asset events are tracked before the plugin configured the tracker
+        app.track_asset_events::<Loading, Level>()
+            .add_plugins(plugin)
+            .insert_resource(server);
+        app.world_mut()
+            .resource_mut::<TrackedAssets<Loading>>()
+            .track(AssetId::<Level>::invalid());
+        app
+    }
+
+    fn loaded_count_after_event(plugin: ProgressPlugin<Loading>) -> ProgressCount {
+        let mut app = app_tracking_events(plugin);
+        app.add_systems(
+            PostUpdate,
+            (|mut events: EventWriter<AssetEvent<Level>>, mut frame: Local<usize>| {
+                *frame += 1;
+                if *frame == 2 {
+                    events.write(AssetEvent::LoadedWithDependencies {
+                        id: AssetId::invalid(),
+                    });
+                }
+            })
+            .in_set(AssetEvents),
+        );
+        app.update();
+        app.update();
+        app.world().resource::<Progress<Loading>>().count()
+    }
+
+    #[test]
+    fn strict_same_frame_resolves_events_without_latency() {
+        assert_eq!(
+            loaded_count_after_event(ProgressPlugin::new()),
+            ProgressCount::pending(1)
+        );
+        assert_eq!(
+            loaded_count_after_event(ProgressPlugin::new().with_strict_same_frame()),
+            ProgressCount::done(1)
+        );
+    }
+
+    #[test]
+    fn failed_assets_do_not_block_completion() {
+        let mut app = app_tracking_events(ProgressPlugin::new());
+        app.add_systems(
+            PostUpdate,
+            (|mut events: EventWriter<AssetLoadFailedEvent<Level>>, mut frame: Local<usize>| {
+                *frame += 1;
+                if *frame == 1 {
+                    events.write(AssetLoadFailedEvent {
+                        id: AssetId::invalid(),
+                        path: AssetPath::from("level.ron"),
+                        error: AssetLoadError::AssetMetaReadError,
+                    });
+                }
+            })
+            .in_set(AssetEvents),
+        );
+        app.update();
+        assert_eq!(
+            app.world().resource::<Progress<Loading>>().count(),
+            ProgressCount::pending(1)
+        );
+        app.update();
+        assert!(app.world().resource::<Progress<Loading>>().is_ready());
+    }
+
+    #[test]
+    fn plugin_polls_tracked_assets_of_asset_plugin() {
+        let mut app = App::new();
+        app.add_plugins((AssetPlugin::default(), ProgressPlugin::<Loading>::new()));
+        app.world_mut()
+            .resource_mut::<TrackedAssets<Loading>>()
+            .track(AssetId::<Level>::invalid());
+
+        app.update();
+        assert_eq!(
+            app.world().resource::<Progress<Loading>>().count(),
+            ProgressCount::pending(1)
+        );
+    }
+}
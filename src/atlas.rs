@@ -0,0 +1,251 @@
+use std::any::type_name;
+use std::marker::PhantomData;
+
+use bevy::asset::{AssetServer, Assets, Handle, LoadState};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Res, ResMut};
+use bevy::image::{
+    Image, TextureAtlasBuilder, TextureAtlasBuilderError, TextureAtlasLayout, TextureAtlasSources,
+};
+use bevy::log::warn;
+use bevy::math::UVec2;
+
+use crate::{Progress, ProgressCount, ProgressMarker};
+
+/// Resource building texture atlases at runtime as tasks of the tracker `T`
+///
+/// Queued atlases are built with a [`TextureAtlasBuilder`] once all their source images are loaded,
+/// one atlas per frame in `Update`, so the loading bar moves between large atlases.
+/// Sources that failed to load are left out of their atlas with a warning.
+/// Every atlas counts with its [`AtlasWeight`] until it is built. Enable building with
+/// [`ProgressAppExt::build_texture_atlases`](crate::ProgressAppExt::build_texture_atlases).
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{AtlasJobId, TextureAtlasJobs};
+/// # struct Loading;
+/// #[derive(Resource)]
+/// struct Tiles(AtlasJobId);
+///
+/// fn queue_tiles(
+///     mut commands: Commands,
+///     server: Res<AssetServer>,
+///     mut atlases: ResMut<TextureAtlasJobs<Loading>>,
+/// ) {
+///     let sources = ["grass.png", "water.png"].map(|path| server.load(path));
+///     commands.insert_resource(Tiles(atlases.queue(sources)));
+/// }
+///
+/// fn spawn_tiles(tiles: Res<Tiles>, atlases: Res<TextureAtlasJobs<Loading>>) {
+///     if let Some(atlas) = atlases.atlas(tiles.0) {
+///         info!("Tiles are packed into {:?}", atlas.image);
+///     }
+/// }
+/// ```
+#[derive(Resource)]
+pub struct TextureAtlasJobs<T> {
+    jobs: Vec<AtlasJob>,
+    weight: AtlasWeight,
+    padding: UVec2,
+    max_size: UVec2,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// How much a queued atlas of [`TextureAtlasJobs`] weighs in its tracker
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AtlasWeight {
+    /// Every source image counts as one task
+    #[default]
+    Regions,
+    /// Every byte of image data counts as one task
+    ///
+    /// Sources that are still loading are weighed once they are loaded,
+    /// while an atlas without any loaded source counts as a single task.
+    Bytes,
+}
+
+/// Id of an atlas queued in [`TextureAtlasJobs`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AtlasJobId(usize);
+
+/// Texture atlas built by [`TextureAtlasJobs`]
+#[derive(Debug)]
+pub struct BuiltAtlas {
+    /// Layout of the regions in the atlas
+    pub layout: Handle<TextureAtlasLayout>,
+    /// Indices of the source images in the layout
+    pub sources: TextureAtlasSources,
+    /// Atlas texture
+    pub image: Handle<Image>,
+}
+
+struct AtlasJob {
+    sources: Vec<Handle<Image>>,
+    built: Option<Result<BuiltAtlas, TextureAtlasBuilderError>>,
+}
+
+impl<T> Default for TextureAtlasJobs<T> {
+    fn default() -> Self {
+        TextureAtlasJobs {
+            jobs: Vec::new(),
+            weight: AtlasWeight::default(),
+            padding: UVec2::ZERO,
+            max_size: UVec2::splat(2048),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> TextureAtlasJobs<T> {
+    /// Queue an atlas packing the given source images
+    pub fn queue(&mut self, sources: impl IntoIterator<Item = Handle<Image>>) -> AtlasJobId {
+        self.jobs.push(AtlasJob {
+            sources: sources.into_iter().collect(),
+            built: None,
+        });
+        AtlasJobId(self.jobs.len() - 1)
+    }
+
+    /// Returns the atlas of the job once it is built
+    ///
+    /// Returns `None` while the job is pending and if building the atlas failed.
+    pub fn atlas(&self, job: AtlasJobId) -> Option<&BuiltAtlas> {
+        self.jobs.get(job.0)?.built.as_ref()?.as_ref().ok()
+    }
+
+    /// Returns `true` once the atlas of the job is built or failed to build
+    pub fn is_done(&self, job: AtlasJobId) -> bool {
+        self.jobs.get(job.0).is_some_and(|job| job.built.is_some())
+    }
+
+    /// Set how much queued atlases weigh in the tracker
+    pub fn set_weight(&mut self, weight: AtlasWeight) {
+        self.weight = weight;
+    }
+
+    /// Set the padding in pixels between the regions of atlases built from now on
+    pub fn set_padding(&mut self, padding: UVec2) {
+        self.padding = padding;
+    }
+
+    /// Set the maximum size in pixels of atlases built from now on
+    ///
+    /// Atlases that do not fit are not built, with a warning. The default is `2048` by `2048`.
+    pub fn set_max_size(&mut self, max_size: UVec2) {
+        self.max_size = max_size;
+    }
+
+    /// Returns the done and total tasks of all queued atlases
+    pub fn count(&self, images: &Assets<Image>) -> ProgressCount {
+        self.jobs
+            .iter()
+            .map(|job| {
+                let tasks = match self.weight {
+                    AtlasWeight::Regions => job.sources.len(),
+                    AtlasWeight::Bytes => job
+                        .sources
+                        .iter()
+                        .filter_map(|source| images.get(source)?.data.as_ref())
+                        .map(Vec::len)
+                        .sum::<usize>()
+                        .max(1),
+                };
+                if job.built.is_some() {
+                    ProgressCount::done(tasks)
+                } else {
+                    ProgressCount::pending(tasks)
+                }
+            })
+            .sum()
+    }
+
+    fn build_next(
+        &mut self,
+        server: Option<&AssetServer>,
+        images: &mut Assets<Image>,
+        layouts: &mut Assets<TextureAtlasLayout>,
+    ) {
+        let failed = |source: &Handle<Image>| {
+            server.is_some_and(|server| {
+                matches!(server.get_load_state(source), Some(LoadState::Failed(_)))
+            })
+        };
+        let Some(job) = self.jobs.iter_mut().find(|job| {
+            job.built.is_none()
+                && job
+                    .sources
+                    .iter()
+                    .all(|source| images.contains(source) || failed(source))
+        }) else {
+            return;
+        };
+        let mut builder = TextureAtlasBuilder::default();
+        builder.padding(self.padding).max_size(self.max_size);
+        for source in &job.sources {
+            match images.get(source) {
+                Some(image) => {
+                    builder.add_texture(Some(source.id()), image);
+                }
+                None => warn!("Leaving the source {source:?} that failed to load out of its atlas"),
+            }
+        }
+        let built = builder.build().map(|(layout, sources, image)| BuiltAtlas {
+            layout: layouts.add(layout),
+            sources,
+            image: images.add(image),
+        });
+        if let Err(error) = &built {
+            warn!("Failed to build a texture atlas: {error}");
+        }
+        job.built = Some(built);
+    }
+}
+
+pub(crate) fn build_texture_atlases<T: ProgressMarker>(
+    mut jobs: ResMut<TextureAtlasJobs<T>>,
+    server: Option<Res<AssetServer>>,
+    mut images: ResMut<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut progress: ResMut<Progress<T>>,
+) {
+    jobs.build_next(server.as_deref(), &mut images, &mut layouts);
+    progress.track_source(type_name::<TextureAtlasJobs<T>>(), jobs.count(&images));
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::asset::{AssetApp, AssetPlugin, Assets};
+    use bevy::image::{Image, TextureAtlasLayout};
+
+    use crate::testing::{self, Loading};
+    use crate::{AtlasWeight, ProgressAppExt, ProgressCount, TextureAtlasJobs};
+
+    #[test]
+    fn builds_one_atlas_per_frame() {
+        let mut app = testing::app();
+        app.add_plugins(AssetPlugin::default())
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .build_texture_atlases::<Loading>();
+        let mut images = app.world_mut().resource_mut::<Assets<Image>>();
+        let sources = [images.add(Image::default()), images.add(Image::default())];
+        let pending = images.reserve_handle();
+        let mut atlases = app.world_mut().resource_mut::<TextureAtlasJobs<Loading>>();
+        atlases.set_weight(AtlasWeight::Bytes);
+        let small = atlases.queue([sources[0].clone()]);
+        let large = atlases.queue([sources[1].clone(), pending.clone()]);
+
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::new(4, 8));
+
+        app.world_mut()
+            .resource_mut::<Assets<Image>>()
+            .insert(&pending, Image::default());
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::done(12));
+        let atlases = app.world().resource::<TextureAtlasJobs<Loading>>();
+        assert!(atlases.is_done(small));
+        let atlas = atlases.atlas(large).unwrap();
+        let layouts = app.world().resource::<Assets<TextureAtlasLayout>>();
+        assert_eq!(layouts.get(&atlas.layout).unwrap().textures.len(), 2);
+    }
+}
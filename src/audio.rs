@@ -0,0 +1,256 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::audio::{AudioSinkPlayback, Volume};
+use bevy::ecs::change_detection::DetectChanges;
+use bevy::ecs::component::{Component, Mutable};
+use bevy::ecs::system::{Local, Query, Res};
+use bevy::time::{Real, Time};
+
+use crate::{Progress, ProgressMarker};
+
+/// Component fading the audio of its entity across the completion of the tracker `T`
+///
+/// Add a [fading out](ProgressCrossfade::fade_out) crossfade to the loading screen music and a
+/// [fading in](ProgressCrossfade::fade_in) one to the gameplay music, next to their `AudioPlayer`s.
+/// Once the tracker tracked tasks and [is ready](Progress::is_ready), the loading screen music fades out
+/// while the gameplay music fades in. The fades reverse when the tracker starts tracking tasks that are not done again.
+/// Fades run in real time and set the volume of the `AudioSink` or `SpatialAudioSink` of the entity,
+/// which is paused while it is silent. Enable fading with
+/// [`ProgressAppExt::crossfade_audio`](crate::ProgressAppExt::crossfade_audio).
+/// ```edition2021
+/// # use std::time::Duration;
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::ProgressCrossfade;
+/// # struct Loading;
+/// fn play_music(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         AudioPlayer::new(server.load("loading.ogg")),
+///         PlaybackSettings::LOOP,
+///         ProgressCrossfade::<Loading>::fade_out(Duration::from_secs(2)),
+///     ));
+///     commands.spawn((
+///         AudioPlayer::new(server.load("gameplay.ogg")),
+///         PlaybackSettings::LOOP,
+///         ProgressCrossfade::<Loading>::fade_in(Duration::from_secs(4)).with_volume(0.8),
+///     ));
+/// }
+/// ```
+#[derive(Component)]
+pub struct ProgressCrossfade<T> {
+    fade_in: bool,
+    duration: Duration,
+    volume: f32,
+    level: f32,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ProgressCrossfade<T> {
+    /// Fade the audio out over the given duration once the tracker is done
+    pub fn fade_out(duration: Duration) -> Self {
+        ProgressCrossfade {
+            fade_in: false,
+            duration,
+            volume: 1.,
+            level: 1.,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Fade the audio in over the given duration once the tracker is done
+    ///
+    /// The audio is silent until then.
+    pub fn fade_in(duration: Duration) -> Self {
+        ProgressCrossfade {
+            fade_in: true,
+            duration,
+            volume: 1.,
+            level: 0.,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Use the given linear volume for the audio when it is not faded out
+    ///
+    /// The default is `1`, which replaces the volume of the playback settings.
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Returns the current linear volume of the audio
+    pub fn volume(&self) -> f32 {
+        self.volume * self.level
+    }
+}
+
+pub(crate) fn crossfade_audio<T, S>(
+    progress: Res<Progress<T>>,
+    time: Res<Time<Real>>,
+    mut complete: Local<bool>,
+    mut sinks: Query<(&mut ProgressCrossfade<T>, &mut S)>,
+) where
+    T: ProgressMarker,
+    S: Component<Mutability = Mutable> + AudioSinkPlayback,
+{
+    // Without tasks, the tracker is neither loading nor done, so the audio keeps fading as before
+    if progress.count().total > 0 {
+        *complete = progress.is_ready();
+    }
+    for (mut crossfade, mut sink) in &mut sinks {
+        let target = if crossfade.fade_in == *complete {
+            1.
+        } else {
+            0.
+        };
+        let step = if crossfade.duration.is_zero() {
+            1.
+        } else {
+            time.delta_secs() / crossfade.duration.as_secs_f32()
+        };
+        let previous = crossfade.level;
+        let level = if target > previous {
+            (previous + step).min(target)
+        } else {
+            (previous - step).max(target)
+        };
+        if level == previous && !sink.is_added() {
+            continue;
+        }
+        crossfade.level = level;
+        sink.set_volume(Volume::Linear(crossfade.volume()));
+        if level == 0. {
+            sink.pause();
+        } else if previous == 0. {
+            sink.play();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    use bevy::app::Update;
+    use bevy::audio::{AudioSinkPlayback, Volume};
+    use bevy::ecs::component::Component;
+    use bevy::ecs::system::{Res, ResMut};
+    use bevy::time::{TimePlugin, TimeUpdateStrategy};
+
+    use crate::testing::{self, Loading, Ready};
+    use crate::{Progress, ProgressCrossfade};
+
+    #[derive(Component)]
+    struct FakeSink {
+        volume: f32,
+        paused: AtomicBool,
+    }
+
+    impl AudioSinkPlayback for FakeSink {
+        fn volume(&self) -> Volume {
+            Volume::Linear(self.volume)
+        }
+
+        fn set_volume(&mut self, volume: Volume) {
+            self.volume = volume.to_linear();
+        }
+
+        fn speed(&self) -> f32 {
+            1.
+        }
+
+        fn set_speed(&self, _speed: f32) {}
+
+        fn play(&self) {
+            self.paused.store(false, Ordering::Relaxed);
+        }
+
+        fn pause(&self) {
+            self.paused.store(true, Ordering::Relaxed);
+        }
+
+        fn is_paused(&self) -> bool {
+            self.paused.load(Ordering::Relaxed)
+        }
+
+        fn stop(&self) {}
+
+        fn empty(&self) -> bool {
+            false
+        }
+
+        fn is_muted(&self) -> bool {
+            false
+        }
+
+        fn mute(&mut self) {}
+
+        fn unmute(&mut self) {}
+    }
+
+    fn sink() -> FakeSink {
+        FakeSink {
+            volume: 1.,
+            paused: AtomicBool::new(false),
+        }
+    }
+
+    #[test]
+    fn crossfades_across_completion() {
+        let mut app = testing::app();
+        app.add_plugins(TimePlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                500,
+            )))
+            .insert_resource(Ready(false))
+            .add_systems(
+                Update,
+                |mut progress: ResMut<Progress<Loading>>, ready: Res<Ready>| {
+                    progress.track(1, ready.0 as usize);
+                },
+            );
+        app.add_systems(Update, super::crossfade_audio::<Loading, FakeSink>);
+        let loading = app
+            .world_mut()
+            .spawn((
+                sink(),
+                ProgressCrossfade::<Loading>::fade_out(Duration::from_secs(1)),
+            ))
+            .id();
+        let gameplay = app
+            .world_mut()
+            .spawn((
+                sink(),
+                ProgressCrossfade::<Loading>::fade_in(Duration::from_secs(2)).with_volume(0.8),
+            ))
+            .id();
+        let volumes = |app: &bevy::app::App| {
+            [loading, gameplay].map(|entity| {
+                let sink = app.world().get::<FakeSink>(entity).unwrap();
+                (sink.volume, sink.is_paused())
+            })
+        };
+
+        app.update();
+        app.update();
+        assert_eq!(volumes(&app), [(1., false), (0., true)]);
+
+        app.world_mut().resource_mut::<Ready>().0 = true;
+        app.update();
+        app.update();
+        assert_eq!(volumes(&app), [(0.5, false), (0.2, false)]);
+
+        app.update();
+        app.update();
+        app.update();
+        assert_eq!(volumes(&app), [(0., true), (0.8, false)]);
+        let crossfade = app.world().get::<ProgressCrossfade<Loading>>(gameplay);
+        assert_eq!(crossfade.unwrap().volume(), 0.8);
+
+        app.world_mut().resource_mut::<Ready>().0 = false;
+        app.update();
+        app.update();
+        assert_eq!(volumes(&app), [(0.5, false), (0.6, false)]);
+    }
+}
@@ -0,0 +1,331 @@
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::asset::{Assets, Handle};
+use bevy::color::{Alpha, Color};
+use bevy::ecs::change_detection::DetectChangesMut;
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::hierarchy::ChildOf;
+use bevy::ecs::query::{Added, With, Without};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Local, Query, Res, ResMut};
+use bevy::math::primitives::Rectangle;
+use bevy::math::{Vec2, Vec3};
+use bevy::pbr::{MeshMaterial3d, StandardMaterial};
+use bevy::render::alpha::AlphaMode;
+use bevy::render::camera::Camera;
+use bevy::render::mesh::{Mesh, Mesh3d};
+use bevy::render::view::Visibility;
+use bevy::transform::components::{GlobalTransform, Transform};
+use bevy::transform::TransformSystem;
+
+use crate::{ProgressComponent, ProgressComponentPlugin, ProgressMarker};
+
+/// Plugin spawning a world space progress bar above every entity with a [`ProgressComponent<T>`]
+///
+/// The bars are children of their entities, always face the active camera and fade out with the distance to it.
+/// They are drawn with unlit [`StandardMaterial`]s, so the app needs the `PbrPlugin` of Bevy.
+/// The [`ProgressComponentPlugin<T>`] is added if it is missing.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::ProgressBillboardPlugin;
+/// struct Construction;
+///
+/// App::new().add_plugins(
+///     ProgressBillboardPlugin::<Construction>::new()
+///         .with_offset(Vec3::Y * 3.)
+///         .with_fade(15., 25.),
+/// );
+/// ```
+pub struct ProgressBillboardPlugin<T> {
+    style: BillboardStyle<T>,
+}
+
+impl<T> ProgressBillboardPlugin<T> {
+    /// Create a new plugin for the components with the marker `T`
+    ///
+    /// Bars are `1` wide and `0.12` high, two units above their entities,
+    /// and fade out between a distance of `20` and `30` to the camera.
+    pub fn new() -> Self {
+        ProgressBillboardPlugin {
+            style: BillboardStyle {
+                offset: Vec3::Y * 2.,
+                size: Vec2::new(1., 0.12),
+                fill: Color::srgb(0.2, 0.8, 0.3),
+                background: Color::srgba(0., 0., 0., 0.6),
+                fade: (20., 30.),
+                _marker: PhantomData,
+            },
+        }
+    }
+
+    /// Place the bars at the given offset to their entities
+    pub fn with_offset(mut self, offset: Vec3) -> Self {
+        self.style.offset = offset;
+        self
+    }
+
+    /// Use the given width and height for the bars
+    pub fn with_size(mut self, size: Vec2) -> Self {
+        self.style.size = size;
+        self
+    }
+
+    /// Draw the done part of the bars in the `fill` color over the `background` color
+    pub fn with_colors(mut self, fill: Color, background: Color) -> Self {
+        self.style.fill = fill;
+        self.style.background = background;
+        self
+    }
+
+    /// Fade the bars out between the distances `start` and `end` to the camera
+    ///
+    /// Bars further away than `end` are hidden.
+    pub fn with_fade(mut self, start: f32, end: f32) -> Self {
+        self.style.fade = (start, end);
+        self
+    }
+}
+
+impl<T> Default for ProgressBillboardPlugin<T> {
+    fn default() -> Self {
+        ProgressBillboardPlugin::new()
+    }
+}
+
+impl<T: ProgressMarker> Plugin for ProgressBillboardPlugin<T> {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<ProgressComponentPlugin<T>>() {
+            app.add_plugins(ProgressComponentPlugin::<T>::new());
+        }
+        app.insert_resource(self.style).add_systems(
+            PostUpdate,
+            (spawn_billboards::<T>, update_billboards::<T>)
+                .chain()
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Progress bar spawned by the [`ProgressBillboardPlugin<T>`] as a child of an entity with a [`ProgressComponent<T>`]
+#[derive(Component)]
+pub struct ProgressBillboard<T> {
+    fill: Entity,
+    materials: [Handle<StandardMaterial>; 2],
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Marker of the mesh showing the done part of a [`ProgressBillboard`]
+#[derive(Component)]
+struct BillboardFill;
+
+#[derive(Resource)]
+struct BillboardStyle<T> {
+    offset: Vec3,
+    size: Vec2,
+    fill: Color,
+    background: Color,
+    fade: (f32, f32),
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for BillboardStyle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for BillboardStyle<T> {}
+
+fn unlit(color: Color) -> StandardMaterial {
+    StandardMaterial {
+        base_color: color,
+        unlit: true,
+        alpha_mode: AlphaMode::Blend,
+        ..StandardMaterial::default()
+    }
+}
+
+fn spawn_billboards<T: ProgressMarker>(
+    mut commands: Commands,
+    added: Query<Entity, Added<ProgressComponent<T>>>,
+    style: Res<BillboardStyle<T>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut quad: Local<Option<Handle<Mesh>>>,
+) {
+    for owner in &added {
+        let quad = quad
+            .get_or_insert_with(|| meshes.add(Rectangle::new(1., 1.)))
+            .clone();
+        let background = materials.add(unlit(style.background));
+        let fill = materials.add(unlit(style.fill));
+        let bar = commands
+            .spawn((
+                Transform::from_translation(style.offset),
+                Visibility::default(),
+                ChildOf(owner),
+            ))
+            .id();
+        commands.spawn((
+            Mesh3d(quad.clone()),
+            MeshMaterial3d(background.clone()),
+            Transform::from_scale(style.size.extend(1.)),
+            ChildOf(bar),
+        ));
+        let fill_mesh = commands
+            .spawn((
+                BillboardFill,
+                Mesh3d(quad),
+                MeshMaterial3d(fill.clone()),
+                Transform::from_xyz(0., 0., 0.001).with_scale(Vec3::ZERO),
+                ChildOf(bar),
+            ))
+            .id();
+        commands.entity(bar).insert(ProgressBillboard::<T> {
+            fill: fill_mesh,
+            materials: [fill, background],
+            _marker: PhantomData,
+        });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_billboards<T: ProgressMarker>(
+    mut commands: Commands,
+    style: Res<BillboardStyle<T>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    owners: Query<(&ProgressComponent<T>, &GlobalTransform)>,
+    mut bars: Query<
+        (
+            Entity,
+            &ProgressBillboard<T>,
+            &ChildOf,
+            &mut Transform,
+            &mut Visibility,
+        ),
+        Without<BillboardFill>,
+    >,
+    mut fills: Query<&mut Transform, With<BillboardFill>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Transforms are propagated after this system, so this uses the camera transform of the last frame
+    let camera = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .max_by_key(|(camera, _)| camera.order)
+        .map(|(_, transform)| transform);
+    for (bar, billboard, child_of, mut transform, mut visibility) in &mut bars {
+        let Ok((progress, owner)) = owners.get(child_of.parent()) else {
+            commands.entity(bar).despawn();
+            continue;
+        };
+        if let Ok(mut fill) = fills.get_mut(billboard.fill) {
+            let done = progress.progress().clamp(0., 1.);
+            fill.scale = Vec3::new(style.size.x * done, style.size.y, 1.);
+            fill.translation.x = -style.size.x * (1. - done) / 2.;
+        }
+        let Some(camera) = camera else {
+            continue;
+        };
+        let (_, owner_rotation, _) = owner.to_scale_rotation_translation();
+        transform.rotation = owner_rotation.inverse() * camera.rotation();
+        let distance = camera
+            .translation()
+            .distance(owner.transform_point(style.offset));
+        let (start, end) = style.fade;
+        let fade = 1. - ((distance - start) / (end - start).max(f32::EPSILON)).clamp(0., 1.);
+        visibility.set_if_neq(if fade > 0. {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+        for (handle, color) in billboard
+            .materials
+            .iter()
+            .zip([style.fill, style.background])
+        {
+            let alpha = color.alpha() * fade;
+            if materials
+                .get(handle)
+                .is_some_and(|material| material.base_color.alpha() != alpha)
+            {
+                if let Some(material) = materials.get_mut(handle) {
+                    material.base_color.set_alpha(alpha);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::App;
+    use bevy::asset::{AssetApp, AssetPlugin, Assets};
+    use bevy::color::Alpha;
+    use bevy::ecs::query::With;
+    use bevy::math::Vec3;
+    use bevy::pbr::{MeshMaterial3d, StandardMaterial};
+    use bevy::render::camera::Camera;
+    use bevy::render::mesh::Mesh;
+    use bevy::transform::components::{GlobalTransform, Transform};
+    use bevy::transform::TransformPlugin;
+
+    use crate::{ProgressBillboard, ProgressBillboardPlugin, ProgressComponent};
+
+    struct Construction;
+
+    #[test]
+    fn faces_the_camera_and_fades_with_distance() {
+        let mut app = App::new();
+        app.add_plugins((
+            AssetPlugin::default(),
+            TransformPlugin,
+            ProgressBillboardPlugin::<Construction>::new().with_fade(5., 10.),
+        ))
+        .init_asset::<Mesh>()
+        .init_asset::<StandardMaterial>();
+        let camera = Transform::from_xyz(7.5, 2., 0.).looking_at(Vec3::Y * 2., Vec3::Y);
+        app.world_mut().spawn((Camera::default(), camera));
+        let mut progress = ProgressComponent::<Construction>::default();
+        progress.persist_tasks(2);
+        progress.persist_done(1);
+        let building = app.world_mut().spawn((progress, Transform::default())).id();
+
+        app.update();
+        app.update();
+        app.update();
+
+        let world = app.world_mut();
+        let (bar, transform) = world
+            .query::<(&ProgressBillboard<Construction>, &GlobalTransform)>()
+            .single(world)
+            .unwrap();
+        assert!(transform.rotation().abs_diff_eq(camera.rotation, 1e-5));
+        let fill = world.entity(bar.fill).get::<Transform>().unwrap();
+        assert_eq!(fill.scale.x, 0.5);
+        let background = &bar.materials[1];
+        let alpha = world
+            .resource::<Assets<StandardMaterial>>()
+            .get(background)
+            .unwrap()
+            .base_color
+            .alpha();
+        assert!((alpha - 0.3).abs() < 1e-5);
+
+        world
+            .entity_mut(building)
+            .remove::<ProgressComponent<Construction>>();
+        app.update();
+        let world = app.world_mut();
+        assert_eq!(
+            world
+                .query_filtered::<(), With<MeshMaterial3d<StandardMaterial>>>()
+                .iter(world)
+                .count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(feature = "bevy")]
+use core::any::type_name;
+
+#[cfg(feature = "bevy")]
+use bevy::ecs::component::Component;
+#[cfg(feature = "bevy")]
+use bevy::ecs::resource::Resource;
+#[cfg(feature = "bevy")]
+use bevy::ecs::system::{Query, Res, ResMut};
+
+use crate::ProgressCount;
+#[cfg(feature = "bevy")]
+use crate::{Progress, ProgressMarker};
+
+/// Number of tasks a bounded value is counted as
+const BOUNDED_VALUE_TASKS: usize = 1000;
+
+/// A value between zero and a maximum, like experience towards the next level or the charge of an attack
+///
+#[cfg_attr(
+    feature = "bevy",
+    doc = "Bounded values can be tracked like any other progress with
+[`ProgressAppExt::track_bounded_resource`](crate::ProgressAppExt::track_bounded_resource) and
+[`ProgressAppExt::track_bounded_components`](crate::ProgressAppExt::track_bounded_components).
+This way, all kinds of bars are driven by the same trackers."
+)]
+/// ```edition2021
+/// # use bevy_progress_tracking::{BoundedValueProgress, ProgressCount};
+/// struct Experience {
+///     points: u32,
+///     next_level: u32,
+/// }
+///
+/// impl BoundedValueProgress for Experience {
+///     fn current(&self) -> f32 {
+///         self.points as f32
+///     }
+///
+///     fn max(&self) -> f32 {
+///         self.next_level as f32
+///     }
+/// }
+///
+/// let experience = Experience { points: 30, next_level: 120 };
+/// assert_eq!(experience.count(), ProgressCount::new(250, 1000));
+/// ```
+pub trait BoundedValueProgress {
+    /// Returns the current value
+    fn current(&self) -> f32;
+
+    /// Returns the maximum value
+    fn max(&self) -> f32;
+
+    /// Returns the value as count of 1000 tasks
+    ///
+    /// Values outside of the bounds are clamped. A maximum of zero or less counts as complete.
+    fn count(&self) -> ProgressCount {
+        let max = self.max();
+        if max <= 0. {
+            return ProgressCount::done(BOUNDED_VALUE_TASKS);
+        }
+        let ratio = (self.current() / max).clamp(0., 1.);
+        ProgressCount::new(
+            (ratio * BOUNDED_VALUE_TASKS as f32) as usize,
+            BOUNDED_VALUE_TASKS,
+        )
+    }
+}
+
+/// Tracks the bounded resource `R` as part of the tracker `T` while it exists
+#[cfg(feature = "bevy")]
+pub(crate) fn track_bounded_resource<T: ProgressMarker, R: Resource + BoundedValueProgress>(
+    resource: Option<Res<R>>,
+    mut progress: ResMut<Progress<T>>,
+) {
+    if let Some(resource) = resource {
+        progress.track_source(type_name::<R>(), resource.count());
+    }
+}
+
+/// Tracks the sum of all bounded components `C` as part of the tracker `T`
+#[cfg(feature = "bevy")]
+pub(crate) fn track_bounded_components<T: ProgressMarker, C: Component + BoundedValueProgress>(
+    components: Query<&C>,
+    mut progress: ResMut<Progress<T>>,
+) {
+    let count = components.iter().map(BoundedValueProgress::count).sum();
+    if count != ProgressCount::default() {
+        progress.track_source(type_name::<C>(), count);
+    }
+}
+
+#[cfg(all(test, feature = "bevy"))]
+mod tests {
+    use bevy::ecs::component::Component;
+
+    use crate::testing::{self, Loading};
+    use crate::{BoundedValueProgress, ProgressAppExt, ProgressCount};
+
+    #[test]
+    fn tracks_bounded_components() {
+        #[derive(Component)]
+        struct Charge(f32);
+
+        impl BoundedValueProgress for Charge {
+            fn current(&self) -> f32 {
+                self.0
+            }
+
+            fn max(&self) -> f32 {
+                2.
+            }
+        }
+
+        let mut app = testing::app();
+        app.track_bounded_components::<Loading, Charge>();
+        app.world_mut().spawn(Charge(1.));
+        app.world_mut().spawn(Charge(3.));
+
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::new(1500, 2000));
+    }
+}
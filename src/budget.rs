@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use bevy::app::{App, First, Plugin};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::ResMut;
+use bevy::platform::time::Instant;
+
+/// Resource with the time left in the current frame for cooperative loading work
+///
+/// Loaders doing work in chunks can stop once the budget is used up and continue in the next frame,
+/// keeping the loading screen at the target frame rate. The start of every frame is recorded by the
+/// [`ProgressBudgetPlugin`] in [`First`]. Insert the resource before adding the plugin to change
+/// the target frame time.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{Progress, ProgressBudget};
+/// # struct Loading;
+/// # #[derive(Resource)]
+/// # struct Chunks { generated: usize, total: usize }
+/// fn generate_chunks(
+///     budget: Res<ProgressBudget>,
+///     mut chunks: ResMut<Chunks>,
+///     mut progress: ResMut<Progress<Loading>>,
+/// ) {
+///     while chunks.generated < chunks.total && !budget.is_exhausted() {
+///         // generate a chunk
+///         chunks.generated += 1;
+///     }
+///     progress.track(chunks.total, chunks.generated);
+/// }
+/// ```
+#[derive(Resource, Debug)]
+pub struct ProgressBudget {
+    target_frame_time: Duration,
+    headroom: Duration,
+    frame_start: Option<Instant>,
+}
+
+impl Default for ProgressBudget {
+    /// A budget for 60 frames per second
+    fn default() -> Self {
+        ProgressBudget::new(Duration::from_secs(1) / 60)
+    }
+}
+
+impl ProgressBudget {
+    /// Create a budget with the given target frame time
+    pub fn new(target_frame_time: Duration) -> Self {
+        ProgressBudget {
+            target_frame_time,
+            headroom: Duration::ZERO,
+            frame_start: None,
+        }
+    }
+
+    /// Returns the target frame time
+    pub fn target_frame_time(&self) -> Duration {
+        self.target_frame_time
+    }
+
+    /// Set the target frame time
+    pub fn set_target_frame_time(&mut self, target_frame_time: Duration) {
+        self.target_frame_time = target_frame_time;
+    }
+
+    /// Keep the given time of every frame free from loading work
+    ///
+    /// Use this to deprioritize loading while gameplay on the loading screen, like a minigame, needs headroom.
+    pub fn set_headroom(&mut self, headroom: Duration) {
+        self.headroom = headroom;
+    }
+
+    /// Returns the time kept free from loading work
+    pub fn headroom(&self) -> Duration {
+        self.headroom
+    }
+
+    /// Returns the target frame time minus the headroom and the time already used in the current frame
+    ///
+    /// Before the first frame started, no time is used yet.
+    pub fn remaining_frame_budget(&self) -> Duration {
+        let used = self
+            .frame_start
+            .map_or(Duration::ZERO, |start| start.elapsed());
+        self.target_frame_time
+            .saturating_sub(self.headroom)
+            .saturating_sub(used)
+    }
+
+    /// Returns `true` if no time is left in the current frame
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining_frame_budget() == Duration::ZERO
+    }
+}
+
+/// Plugin maintaining the [`ProgressBudget`] resource
+pub struct ProgressBudgetPlugin;
+
+impl Plugin for ProgressBudgetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProgressBudget>().add_systems(
+            First,
+            |mut budget: ResMut<ProgressBudget>| {
+                budget.frame_start = Some(Instant::now());
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::app::{App, Update};
+    use bevy::ecs::system::Res;
+
+    use crate::{ProgressBudget, ProgressBudgetPlugin};
+
+    #[test]
+    fn tracks_remaining_frame_budget() {
+        let mut app = App::new();
+        app.insert_resource(ProgressBudget::new(Duration::from_secs(60)))
+            .add_plugins(ProgressBudgetPlugin)
+            .add_systems(Update, |budget: Res<ProgressBudget>| {
+                assert!(budget.remaining_frame_budget() <= Duration::from_secs(60));
+                assert!(!budget.is_exhausted());
+            });
+        assert_eq!(
+            app.world()
+                .resource::<ProgressBudget>()
+                .remaining_frame_budget(),
+            Duration::from_secs(60)
+        );
+        app.update();
+
+        app.world_mut()
+            .resource_mut::<ProgressBudget>()
+            .set_headroom(Duration::from_secs(60));
+        assert!(app.world().resource::<ProgressBudget>().is_exhausted());
+    }
+}
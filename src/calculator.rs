@@ -0,0 +1,107 @@
+use crate::ProgressCount;
+
+/// Derives the progress ratio of a tracker from its counts
+///
+/// By default, the ratio of a tracker is its done tasks divided by its total tasks.
+/// Set a calculator with [`Progress::set_calculator`](crate::Progress::set_calculator) to change that,
+/// for example to follow a perception curve. Functions and closures taking a [`ProgressCount`] are calculators.
+/// ```edition2021
+/// # use bevy_progress_tracking::{Progress, ProgressCount};
+/// # struct Loading;
+/// let mut progress = Progress::<Loading>::default();
+/// // The last 10% of tasks count double
+/// progress.set_calculator(|count: ProgressCount| {
+///     let ratio = count.ratio();
+///     if ratio < 0.9 { ratio / 1.1 } else { (0.9 + (ratio - 0.9) * 2.) / 1.1 }
+/// });
+/// progress.track(10, 5);
+/// progress.finish_frame();
+/// assert_eq!(progress.progress(), 0.5 / 1.1);
+/// ```
+pub trait ProgressCalculator: Send + Sync + 'static {
+    /// Returns the ratio between 0 and 1 for the given count
+    ///
+    /// Values outside of that range are clamped.
+    fn ratio(&self, count: ProgressCount) -> f32;
+}
+
+impl<F> ProgressCalculator for F
+where
+    F: Fn(ProgressCount) -> f32 + Send + Sync + 'static,
+{
+    fn ratio(&self, count: ProgressCount) -> f32 {
+        self(count)
+    }
+}
+
+/// Ready-made perception curves for the progress ratio
+///
+/// Loading bars often move faster at the start to feel quicker. All curves start at 0 and end at 1.
+#[cfg_attr(
+    feature = "bevy",
+    doc = "Select one with [`ProgressPlugin::with_curve`](crate::ProgressPlugin::with_curve)
+or [`Progress::set_calculator`](crate::Progress::set_calculator)."
+)]
+#[cfg_attr(
+    not(feature = "bevy"),
+    doc = "Select one with [`Progress::set_calculator`](crate::Progress::set_calculator)."
+)]
+/// ```edition2021
+/// # use bevy_progress_tracking::{ProgressCalculator, ProgressCount, ProgressCurve};
+/// assert_eq!(ProgressCurve::EaseOut.ratio(ProgressCount::new(1, 2)), 0.75);
+/// assert_eq!(ProgressCurve::Stepped(4).ratio(ProgressCount::new(2, 3)), 0.5);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProgressCurve {
+    /// The ratio of done tasks
+    #[default]
+    Linear,
+    /// Quadratic ease-out, slowing down towards the end
+    EaseOut,
+    /// Cubic ease-out, with a faster start and a slower finish than [`ProgressCurve::EaseOut`]
+    FastStartSlowFinish,
+    /// The ratio rounded down to the given number of steps
+    ///
+    /// Zero steps count as one step.
+    Stepped(u32),
+}
+
+impl ProgressCalculator for ProgressCurve {
+    fn ratio(&self, count: ProgressCount) -> f32 {
+        let ratio = count.ratio();
+        let remaining = 1. - ratio;
+        match self {
+            ProgressCurve::Linear => ratio,
+            ProgressCurve::EaseOut => 1. - remaining * remaining,
+            ProgressCurve::FastStartSlowFinish => 1. - remaining * remaining * remaining,
+            ProgressCurve::Stepped(steps) => {
+                let steps = (*steps).max(1) as f32;
+                ((ratio * steps) as u32) as f32 / steps
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ProgressCalculator, ProgressCount, ProgressCurve};
+
+    #[test]
+    fn curves_start_at_zero_and_end_at_one() {
+        for curve in [
+            ProgressCurve::Linear,
+            ProgressCurve::EaseOut,
+            ProgressCurve::FastStartSlowFinish,
+            ProgressCurve::Stepped(0),
+            ProgressCurve::Stepped(3),
+        ] {
+            assert_eq!(curve.ratio(ProgressCount::pending(5)), 0.);
+            assert_eq!(curve.ratio(ProgressCount::done(5)), 1.);
+        }
+        assert_eq!(
+            ProgressCurve::FastStartSlowFinish.ratio(ProgressCount::new(1, 2)),
+            0.875
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use std::any::type_name;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use bevy::app::{App, Last, Plugin, PreUpdate};
+use bevy::ecs::event::{Event, EventReader, EventWriter};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Local, Res, ResMut};
+use bevy::log::warn;
+
+use crate::file::write_atomic;
+use crate::{Progress, ProgressAppExt, ProgressCount, ProgressMarker};
+
+/// Resource of a long running job, like baking lightmaps, that can be continued from a cursor
+///
+/// Track it with [`ProgressAppExt::track_checkpointed_job`](crate::ProgressAppExt::track_checkpointed_job)
+/// to get a [`ProgressCheckpoint`] event whenever the job made another step of progress.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{CheckpointJob, ProgressCount};
+/// #[derive(Resource)]
+/// struct BakeLightmaps {
+///     baked_chunks: usize,
+///     chunks: usize,
+/// }
+///
+/// impl CheckpointJob for BakeLightmaps {
+///     fn count(&self) -> ProgressCount {
+///         ProgressCount::new(self.baked_chunks, self.chunks)
+///     }
+///
+///     fn cursor(&self) -> String {
+///         self.baked_chunks.to_string()
+///     }
+/// }
+/// ```
+pub trait CheckpointJob: Resource {
+    /// Returns the done and total steps of the job
+    fn count(&self) -> ProgressCount;
+
+    /// Returns where to continue the job after a restart
+    fn cursor(&self) -> String;
+}
+
+/// Event sent when a [`CheckpointJob`] of the tracker `T` reached a checkpoint
+///
+/// Save it to resume the job after a crash.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct ProgressCheckpoint<T> {
+    /// Count of the job at the checkpoint
+    pub count: ProgressCount,
+    /// Cursor of the job at the checkpoint
+    pub cursor: String,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ProgressCheckpoint<T> {
+    /// Create a checkpoint with the given count and cursor
+    pub fn new(count: ProgressCount, cursor: String) -> Self {
+        ProgressCheckpoint {
+            count,
+            cursor,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A [`CheckpointJob`] that can continue from a checkpoint
+///
+/// See [`ResumeJobPlugin`].
+pub trait ResumableJob: CheckpointJob {
+    /// Continue the job from the cursor of a checkpoint with the given count
+    ///
+    /// Afterwards, [`CheckpointJob::count`] should return the count of the checkpoint.
+    fn resume(&mut self, cursor: &str, count: ProgressCount);
+}
+
+/// Plugin saving the checkpoints of the job `J` to a file and resuming the job from it on startup
+///
+/// The job is tracked as part of the tracker `T` like with
+/// [`ProgressAppExt::track_checkpointed_job`]. Every checkpoint overwrites the file. Once the job is complete,
+/// the file is removed, so the next run starts from the beginning. If the file exists on startup,
+/// [`ResumableJob::resume`] is called before the first `Update` the job resource exists in,
+/// and tracking continues from the done count of the checkpoint.
+/// ```edition2021,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{CheckpointJob, ProgressCount, ProgressPlugin, ResumableJob, ResumeJobPlugin};
+/// # #[derive(Resource)]
+/// # struct BakeLightmaps(usize);
+/// # impl CheckpointJob for BakeLightmaps {
+/// #     fn count(&self) -> ProgressCount { ProgressCount::new(self.0, 100) }
+/// #     fn cursor(&self) -> String { self.0.to_string() }
+/// # }
+/// impl ResumableJob for BakeLightmaps {
+///     fn resume(&mut self, _cursor: &str, count: ProgressCount) {
+///         self.0 = count.done;
+///     }
+/// }
+///
+/// struct Baking;
+///
+/// App::new().add_plugins((
+///     ProgressPlugin::<Baking>::new(),
+///     ResumeJobPlugin::<Baking, BakeLightmaps>::new("lightmaps.checkpoint"),
+/// ));
+/// ```
+pub struct ResumeJobPlugin<T, J> {
+    path: PathBuf,
+    interval: f32,
+    _marker: PhantomData<fn() -> (T, J)>,
+}
+
+impl<T, J> ResumeJobPlugin<T, J> {
+    /// Create a plugin saving a checkpoint every 10% of progress to the file at the given path
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        ResumeJobPlugin {
+            path: path.into(),
+            interval: 0.1,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the progress between two checkpoints
+    pub fn with_interval(mut self, interval: f32) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+impl<T: ProgressMarker, J: ResumableJob> Plugin for ResumeJobPlugin<T, J> {
+    fn build(&self, app: &mut App) {
+        let mut checkpoint = match load_checkpoint(&self.path) {
+            Ok(checkpoint) => Some(checkpoint),
+            Err(error) => {
+                if error.kind() != io::ErrorKind::NotFound {
+                    warn!(
+                        "Failed to load checkpoint from '{}': {}",
+                        self.path.display(),
+                        error
+                    );
+                }
+                None
+            }
+        };
+        let path = self.path.clone();
+        app.track_checkpointed_job::<T, J>(self.interval)
+            .add_systems(PreUpdate, move |job: Option<ResMut<J>>| {
+                if let (Some(mut job), Some((count, cursor))) = (job, checkpoint.as_ref()) {
+                    job.resume(cursor, *count);
+                    checkpoint = None;
+                }
+            })
+            .add_systems(
+                Last,
+                move |job: Option<Res<J>>,
+                      mut checkpoints: EventReader<ProgressCheckpoint<T>>,
+                      mut removed: Local<bool>| {
+                    let complete = job.is_some_and(|job| job.count().is_complete());
+                    let saved = if complete {
+                        (!*removed).then(|| fs::remove_file(&path))
+                    } else {
+                        checkpoints
+                            .read()
+                            .last()
+                            .map(|checkpoint| save_checkpoint(&path, checkpoint))
+                    };
+                    *removed = complete;
+                    match saved {
+                        Some(Err(error)) if error.kind() != io::ErrorKind::NotFound => warn!(
+                            "Failed to update checkpoint at '{}': {}",
+                            path.display(),
+                            error
+                        ),
+                        _ => {}
+                    }
+                },
+            );
+    }
+}
+
+fn save_checkpoint<T>(path: &Path, checkpoint: &ProgressCheckpoint<T>) -> io::Result<()> {
+    write_atomic(
+        path,
+        format!(
+            "{} {}\n{}",
+            checkpoint.count.done, checkpoint.count.total, checkpoint.cursor
+        ),
+    )
+}
+
+fn load_checkpoint(path: &Path) -> io::Result<(ProgressCount, String)> {
+    let content = fs::read_to_string(path)?;
+    let (count, cursor) = content.split_once('\n').unwrap_or((&content, ""));
+    let count = count
+        .split_once(' ')
+        .and_then(|(done, total)| Some(ProgressCount::new(done.parse().ok()?, total.parse().ok()?)))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid checkpoint count '{count}'"),
+            )
+        })?;
+    Ok((count, cursor.to_owned()))
+}
+
+/// Returns the index of the last checkpoint the count passed, with checkpoints every `interval` of progress
+///
+/// The interval is rounded to per mille to get the same checkpoints on all platforms.
+/// Intervals below one per mille place a checkpoint at every done step.
+fn checkpoint_index(count: ProgressCount, interval: f32) -> usize {
+    let step = (interval * 1000.).round() as u32;
+    if step == 0 {
+        return count.done;
+    }
+    (count.per_mille() / step) as usize
+}
+
+/// Returns a system tracking the job `J` and sending a [`ProgressCheckpoint`] every `interval` of progress
+///
+/// The system has to run only while the job exists.
+pub(crate) fn send_checkpoints<T: ProgressMarker, J: CheckpointJob>(
+    interval: f32,
+) -> impl FnMut(Res<J>, ResMut<Progress<T>>, EventWriter<ProgressCheckpoint<T>>) {
+    let mut passed = None;
+    move |job, mut progress, mut checkpoints| {
+        let count = job.count();
+        progress.track_source(type_name::<J>(), count);
+        let index = checkpoint_index(count, interval);
+        // The progress a job was started or resumed with is no checkpoint
+        if passed.is_some_and(|passed| index > passed) {
+            checkpoints.write(ProgressCheckpoint::new(count, job.cursor()));
+        }
+        passed = Some(index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use bevy::app::Update;
+    use bevy::ecs::event::Events;
+    use bevy::ecs::resource::Resource;
+    use bevy::ecs::system::ResMut;
+
+    use crate::checkpoint::checkpoint_index;
+    use crate::testing::{self, Loading};
+    use crate::{
+        CheckpointJob, ProgressAppExt, ProgressCheckpoint, ProgressCount, ResumableJob,
+        ResumeJobPlugin,
+    };
+
+    #[derive(Resource)]
+    struct Bake(usize);
+
+    impl CheckpointJob for Bake {
+        fn count(&self) -> ProgressCount {
+            ProgressCount::new(self.0, 10)
+        }
+
+        fn cursor(&self) -> String {
+            format!("chunk {}", self.0)
+        }
+    }
+
+    impl ResumableJob for Bake {
+        fn resume(&mut self, cursor: &str, count: ProgressCount) {
+            assert_eq!(cursor, "chunk 6");
+            self.0 = count.done;
+        }
+    }
+
+    #[test]
+    fn resumes_from_saved_checkpoint() {
+        let path = std::env::temp_dir().join("bevy_progress_tracking_resume_test.checkpoint");
+        fs::write(&path, "6 10\nchunk 6").unwrap();
+        let mut app = testing::app();
+        app.add_plugins(ResumeJobPlugin::<Loading, Bake>::new(&path).with_interval(0.2))
+            .insert_resource(Bake(0))
+            .add_systems(Update, |mut bake: ResMut<Bake>| bake.0 += 1);
+
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::new(7, 10));
+        app.update();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "8 10\nchunk 8");
+        app.update();
+        app.update();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn sends_checkpoints_of_jobs() {
+        let mut app = testing::app();
+        app.track_checkpointed_job::<Loading, Bake>(0.25)
+            .insert_resource(Bake(1))
+            .add_systems(Update, |mut bake: ResMut<Bake>| bake.0 += 1);
+        let mut cursors = Vec::new();
+        for _ in 0..9 {
+            app.update();
+            let events = app
+                .world()
+                .resource::<Events<ProgressCheckpoint<Loading>>>();
+            cursors.extend(
+                events
+                    .iter_current_update_events()
+                    .map(|checkpoint| checkpoint.cursor.clone()),
+            );
+        }
+        assert_eq!(cursors, ["chunk 3", "chunk 5", "chunk 8", "chunk 10"]);
+        assert!(testing::count(&app).is_complete());
+    }
+
+    #[test]
+    fn counts_passed_checkpoints() {
+        assert_eq!(checkpoint_index(ProgressCount::new(0, 10), 0.25), 0);
+        assert_eq!(checkpoint_index(ProgressCount::new(2, 10), 0.25), 0);
+        assert_eq!(checkpoint_index(ProgressCount::new(3, 10), 0.1), 3);
+        assert_eq!(checkpoint_index(ProgressCount::new(5, 10), 0.25), 2);
+        assert_eq!(checkpoint_index(ProgressCount::done(10), 0.25), 4);
+        assert_eq!(checkpoint_index(ProgressCount::new(7, 10), 0.), 7);
+    }
+}
@@ -0,0 +1,181 @@
+use std::marker::PhantomData;
+
+use bevy::ecs::bundle::Bundle;
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::With;
+use bevy::ecs::system::{Commands, Local, Query, Res};
+
+use crate::{Progress, ProgressMarker};
+
+/// Marker component to despawn the entity when the tracker `T` is done
+///
+/// The entity is despawned at the end of the first frame the tracker tracked tasks and [is ready](Progress::is_ready) in.
+/// A tracker without any tasks is not considered done.
+/// Despawning is handled by the [`ProgressPlugin`](crate::ProgressPlugin).
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::DespawnOnProgressComplete;
+/// # struct Loading;
+/// fn spawn_loading_screen(mut commands: Commands) {
+///     commands.spawn((Name::new("Loading screen"), DespawnOnProgressComplete::<Loading>::default()));
+/// }
+/// ```
+#[derive(Component)]
+pub struct DespawnOnProgressComplete<T: ProgressMarker> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: ProgressMarker> Default for DespawnOnProgressComplete<T> {
+    fn default() -> Self {
+        DespawnOnProgressComplete {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Marker component to despawn the entity when the tracker `T` starts tracking tasks that are not done
+///
+/// The entity is despawned at the end of the first frame the tracker tracked tasks and is not [ready](Progress::is_ready) in.
+/// Despawning is handled by the [`ProgressPlugin`](crate::ProgressPlugin).
+#[derive(Component)]
+pub struct DespawnOnProgressStart<T: ProgressMarker> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: ProgressMarker> Default for DespawnOnProgressStart<T> {
+    fn default() -> Self {
+        DespawnOnProgressStart {
+            _marker: PhantomData,
+        }
+    }
+}
+
+pub(crate) fn despawn_on_progress<T: ProgressMarker>(
+    mut commands: Commands,
+    progress: Res<Progress<T>>,
+    mut was_ready: Local<Option<bool>>,
+    complete: Query<Entity, With<DespawnOnProgressComplete<T>>>,
+    start: Query<Entity, With<DespawnOnProgressStart<T>>>,
+) {
+    // Without tasks, the tracker is neither loading nor done
+    let ready = (progress.count().total > 0).then(|| progress.is_ready());
+    if *was_ready == ready {
+        return;
+    }
+    *was_ready = ready;
+    let Some(ready) = ready else {
+        return;
+    };
+    let entities: Vec<Entity> = if ready {
+        complete.iter().collect()
+    } else {
+        start.iter().collect()
+    };
+    for entity in entities {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Returns a system spawning the bundle of the factory once the tracker `T` is done
+pub(crate) fn spawn_on_complete<T: ProgressMarker, B: Bundle>(
+    factory: impl FnOnce() -> B + Send + Sync + 'static,
+) -> impl FnMut(Commands, Res<Progress<T>>) {
+    let mut factory = Some(factory);
+    move |mut commands, progress| {
+        if progress.count().total == 0 || !progress.is_ready() {
+            return;
+        }
+        if let Some(factory) = factory.take() {
+            commands.spawn(factory());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, Update};
+    use bevy::ecs::query::With;
+    use bevy::ecs::resource::Resource;
+    use bevy::ecs::system::{Res, ResMut};
+
+    use crate::testing::{self, Pending, Ready};
+    use crate::{
+        DespawnOnProgressComplete, DespawnOnProgressStart, Progress, ProgressAppExt, ProgressPlugin,
+    };
+
+    struct Loading;
+
+    #[derive(Resource)]
+    struct Done(usize);
+
+    #[test]
+    fn despawns_on_start_and_complete() {
+        let mut app = App::new();
+        app.add_plugins(ProgressPlugin::<Loading>::new())
+            .insert_resource(Done(0))
+            .add_systems(
+                Update,
+                |done: Res<Done>, mut progress: ResMut<Progress<Loading>>| {
+                    progress.track(1, done.0)
+                },
+            );
+        let menu = app
+            .world_mut()
+            .spawn(DespawnOnProgressStart::<Loading>::default())
+            .id();
+        let loading_screen = app
+            .world_mut()
+            .spawn(DespawnOnProgressComplete::<Loading>::default())
+            .id();
+
+        app.update();
+        app.update();
+        assert!(app.world().get_entity(menu).is_err());
+        assert!(app.world().get_entity(loading_screen).is_ok());
+
+        app.world_mut().resource_mut::<Done>().0 = 1;
+        app.update();
+        assert!(app.world().get_entity(loading_screen).is_err());
+    }
+
+    #[test]
+    fn keeps_entities_without_tasks() {
+        let mut app = App::new();
+        app.add_plugins(ProgressPlugin::<Loading>::new());
+        let loading_screen = app
+            .world_mut()
+            .spawn(DespawnOnProgressComplete::<Loading>::default())
+            .id();
+
+        app.update();
+        app.update();
+        assert!(app.world().get_entity(loading_screen).is_ok());
+    }
+
+    #[test]
+    fn spawns_bundle_once_on_complete() {
+        let mut app = testing::app();
+        app.insert_resource(Ready(false))
+            .add_systems(
+                Update,
+                |ready: Res<Ready>, mut progress: ResMut<Progress<testing::Loading>>| {
+                    progress.track(1, ready.0 as usize)
+                },
+            )
+            .spawn_on_complete::<testing::Loading, _>(|| Pending);
+        let spawned = |app: &mut App| {
+            app.world_mut()
+                .query_filtered::<(), With<Pending>>()
+                .iter(app.world())
+                .count()
+        };
+
+        app.update();
+        assert_eq!(spawned(&mut app), 0);
+        app.world_mut().resource_mut::<Ready>().0 = true;
+        app.update();
+        app.update();
+        assert_eq!(spawned(&mut app), 1);
+    }
+}
@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use bevy::app::{App, First, Plugin};
+#[cfg(feature = "window")]
+use bevy::ecs::event::{EventReader, Events};
+use bevy::ecs::resource::Resource;
+#[cfg(feature = "window")]
+use bevy::ecs::schedule::common_conditions::resource_exists;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Res, ResMut};
+use bevy::platform::time::Instant;
+use bevy::time::{Real, Time, TimeSystem};
+#[cfg(feature = "window")]
+use bevy::window::WindowOccluded;
+
+/// Resource with the time used by timing based features of all trackers
+///
+/// The clock follows the real [`Time`] or, without the `TimePlugin`, the system clock,
+/// but does not advance while it is paused. Stall detection and durations
+/// of the [`SessionReportPlugin`](crate::SessionReportPlugin), the [loading time](crate::Progress::loading_time)
+/// and [ETA](crate::Progress::eta) of trackers and the rotation of [`LoadingTips`](crate::LoadingTips) use this clock,
+/// so a paused app does not report stalls or inflated loading times. The [`ProgressPlugin`](crate::ProgressPlugin) adds the clock and advances it in [`First`].
+///
+/// With the `window` feature, the clock pauses while a window is occluded, for example because the browser
+/// tab of a web build is hidden. Browsers stop producing frames for hidden tabs, so the first frame after
+/// a window was occluded or revealed is not counted either.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::ProgressClock;
+/// fn pause_on_menu(mut clock: ResMut<ProgressClock>) {
+///     clock.pause();
+/// }
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct ProgressClock {
+    elapsed: Duration,
+    delta: Duration,
+    last_frame: Option<Instant>,
+    paused: bool,
+    skip_frame: bool,
+}
+
+impl ProgressClock {
+    /// Returns the time the clock advanced since it was created
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the time the clock advanced in the current frame
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// Returns `true` if the clock is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Stop advancing the clock until it is resumed
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Continue advancing the clock with the next frame
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    fn tick(&mut self, delta: Duration) {
+        let skip_frame = core::mem::take(&mut self.skip_frame);
+        self.delta = if self.paused || skip_frame {
+            Duration::ZERO
+        } else {
+            delta
+        };
+        self.elapsed += self.delta;
+    }
+}
+
+/// Plugin maintaining the [`ProgressClock`], added by every [`ProgressPlugin`](crate::ProgressPlugin)
+pub(crate) struct ProgressClockPlugin;
+
+impl Plugin for ProgressClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProgressClock>()
+            .add_systems(First, tick_clock.after(TimeSystem));
+        #[cfg(feature = "window")]
+        app.add_systems(
+            First,
+            pause_while_occluded
+                .before(tick_clock)
+                .run_if(resource_exists::<Events<WindowOccluded>>),
+        );
+    }
+}
+
+fn tick_clock(mut clock: ResMut<ProgressClock>, time: Option<Res<Time<Real>>>) {
+    let now = Instant::now();
+    let delta = match time {
+        Some(time) => time.delta(),
+        None => clock.last_frame.map_or(Duration::ZERO, |last| now - last),
+    };
+    clock.last_frame = Some(now);
+    clock.tick(delta);
+}
+
+#[cfg(feature = "window")]
+fn pause_while_occluded(mut events: EventReader<WindowOccluded>, mut clock: ResMut<ProgressClock>) {
+    for event in events.read() {
+        clock.paused = event.occluded;
+        // The time since the last frame might have passed while the window was occluded
+        clock.skip_frame = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::ProgressClock;
+
+    #[test]
+    fn does_not_advance_while_paused() {
+        let mut clock = ProgressClock::default();
+        clock.tick(Duration::from_secs(1));
+        assert_eq!(clock.elapsed(), Duration::from_secs(1));
+
+        clock.pause();
+        clock.tick(Duration::from_secs(4));
+        assert_eq!(clock.delta(), Duration::ZERO);
+        clock.resume();
+        clock.tick(Duration::from_secs(1));
+        assert_eq!(clock.delta(), Duration::from_secs(1));
+        assert_eq!(clock.elapsed(), Duration::from_secs(2));
+    }
+
+    #[cfg(feature = "window")]
+    #[test]
+    fn pauses_while_window_is_occluded() {
+        use bevy::app::App;
+        use bevy::ecs::entity::Entity;
+        use bevy::time::{TimePlugin, TimeUpdateStrategy};
+        use bevy::window::WindowOccluded;
+
+        use crate::clock::ProgressClockPlugin;
+
+        let mut app = App::new();
+        app.add_event::<WindowOccluded>()
+            .add_plugins((TimePlugin, ProgressClockPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(1)));
+        app.update();
+        app.update();
+        assert_eq!(
+            app.world().resource::<ProgressClock>().delta(),
+            Duration::from_secs(1)
+        );
+
+        app.world_mut().send_event(WindowOccluded {
+            window: Entity::PLACEHOLDER,
+            occluded: true,
+        });
+        app.update();
+        assert!(app.world().resource::<ProgressClock>().is_paused());
+
+        app.world_mut().send_event(WindowOccluded {
+            window: Entity::PLACEHOLDER,
+            occluded: false,
+        });
+        app.update();
+        let clock = app.world().resource::<ProgressClock>();
+        assert!(!clock.is_paused());
+        assert_eq!(clock.delta(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,244 @@
+use std::any::type_name;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use bevy::app::{App, Last, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::query::QueryFilter;
+use bevy::ecs::schedule::common_conditions::not;
+use bevy::ecs::schedule::{IntoScheduleConfigs, SystemSet};
+use bevy::ecs::system::{Query, ResMut};
+
+use crate::plugin::{tracker_built, with_tracker_schedules};
+use crate::{Progress, ProgressCount, ProgressMarker, ProgressSystems};
+
+/// Component tracking the progress of a single entity, like the construction of a building
+///
+/// It dereferences to a [`Progress`] with its own counts and frames.
+/// Add a [`ProgressComponentPlugin`] with the same marker to finish the frames of all entities.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{ProgressComponent, ProgressComponentPlugin};
+/// struct Construction;
+///
+/// #[derive(Component)]
+/// struct Building {
+///     walls: usize,
+/// }
+///
+/// fn build(mut buildings: Query<(&Building, &mut ProgressComponent<Construction>)>) {
+///     for (building, mut progress) in &mut buildings {
+///         progress.track(4, building.walls);
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins(ProgressComponentPlugin::<Construction>::new())
+///     .add_systems(Update, build);
+/// ```
+#[derive(Component)]
+pub struct ProgressComponent<T: ProgressMarker> {
+    progress: Progress<T>,
+}
+
+impl<T: ProgressMarker> Default for ProgressComponent<T> {
+    fn default() -> Self {
+        ProgressComponent {
+            progress: Progress::default(),
+        }
+    }
+}
+
+impl<T: ProgressMarker> Deref for ProgressComponent<T> {
+    type Target = Progress<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.progress
+    }
+}
+
+impl<T: ProgressMarker> DerefMut for ProgressComponent<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.progress
+    }
+}
+
+/// Plugin finishing the frames of all [`ProgressComponent`]s with the marker `T`
+///
+/// Frames are finished right before the frames of the tracker with the same marker,
+/// in the schedule its [`ProgressPlugin`](crate::ProgressPlugin) finishes frames in.
+/// Without a tracker, they are finished at the end of every frame in [`Last`].
+pub struct ProgressComponentPlugin<T> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ProgressComponentPlugin<T> {
+    /// Create a new plugin for the components with the marker `T`
+    pub fn new() -> Self {
+        ProgressComponentPlugin {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for ProgressComponentPlugin<T> {
+    fn default() -> Self {
+        ProgressComponentPlugin::new()
+    }
+}
+
+impl<T: ProgressMarker> Plugin for ProgressComponentPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Last,
+            finish_component_frames::<T>
+                .in_set(FinishComponentFrames)
+                .run_if(not(tracker_built::<T>)),
+        );
+        with_tracker_schedules::<T>(app, |app, schedules| {
+            app.add_systems(
+                schedules.finish,
+                finish_component_frames::<T>
+                    .in_set(FinishComponentFrames)
+                    .before(ProgressSystems::FinishFrame),
+            );
+        });
+    }
+}
+
+/// System set of the systems finishing the frames of [`ProgressComponent`]s
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct FinishComponentFrames;
+
+pub(crate) fn finish_component_frames<T: ProgressMarker>(
+    mut components: Query<&mut ProgressComponent<T>>,
+) {
+    for mut progress in &mut components {
+        progress.finish_frame();
+    }
+}
+
+/// Tracks the sum of all [`ProgressComponent<T>`] matching the filter `F` as part of the tracker `T`
+pub(crate) fn aggregate_components<T: ProgressMarker, F: QueryFilter>(
+    components: Query<&ProgressComponent<T>, F>,
+    mut progress: ResMut<Progress<T>>,
+) {
+    let count = components.iter().map(|component| component.count()).sum();
+    if count != ProgressCount::default() {
+        progress.track_source(type_name::<F>(), count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, Update};
+    use bevy::ecs::component::Component;
+    use bevy::ecs::query::With;
+    use bevy::ecs::schedule::ScheduleLabel;
+    use bevy::ecs::system::Query;
+
+    use crate::testing::{self, Loading};
+    use crate::{
+        ProgressAppExt, ProgressComponent, ProgressComponentPlugin, ProgressCount, ProgressPlugin,
+    };
+
+    struct Construction;
+
+    #[derive(Component)]
+    struct Walls(usize);
+
+    #[test]
+    fn finishes_frames_of_every_entity() {
+        let mut app = App::new();
+        app.add_plugins(ProgressComponentPlugin::<Construction>::new())
+            .add_systems(
+                Update,
+                |mut buildings: Query<(&Walls, &mut ProgressComponent<Construction>)>| {
+                    for (walls, mut progress) in &mut buildings {
+                        progress.track(2, walls.0);
+                    }
+                },
+            );
+        let tower = app
+            .world_mut()
+            .spawn((Walls(1), ProgressComponent::<Construction>::default()))
+            .id();
+        let wall = app
+            .world_mut()
+            .spawn((Walls(2), ProgressComponent::<Construction>::default()))
+            .id();
+        app.update();
+
+        let count = |entity| {
+            app.world()
+                .get::<ProgressComponent<Construction>>(entity)
+                .unwrap()
+                .count()
+        };
+        assert_eq!(count(tower), ProgressCount::new(1, 2));
+        assert_eq!(count(wall), ProgressCount::done(2));
+    }
+
+    #[test]
+    fn aggregates_progress_components() {
+        #[derive(Component)]
+        struct PlayerBase;
+
+        let mut app = testing::app();
+        app.add_plugins(ProgressComponentPlugin::<Loading>::new())
+            .aggregate_components::<Loading, With<PlayerBase>>()
+            .add_systems(
+                Update,
+                |mut buildings: Query<&mut ProgressComponent<Loading>>| {
+                    for mut building in &mut buildings {
+                        building.track(2, 1);
+                    }
+                },
+            );
+        app.world_mut()
+            .spawn((PlayerBase, ProgressComponent::<Loading>::default()));
+        app.world_mut()
+            .spawn((PlayerBase, ProgressComponent::<Loading>::default()));
+        app.world_mut()
+            .spawn(ProgressComponent::<Loading>::default());
+
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::new(2, 4));
+    }
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct FinishLoading;
+
+    #[test]
+    fn aggregates_in_the_finish_schedule_of_the_tracker() {
+        let mut app = App::new();
+        app.add_plugins(ProgressComponentPlugin::<Loading>::new())
+            .aggregate_components::<Loading, ()>()
+            .add_plugins(ProgressPlugin::<Loading>::new().finish_frame_in(FinishLoading))
+            .add_systems(
+                Update,
+                |mut buildings: Query<&mut ProgressComponent<Loading>>| {
+                    for mut building in &mut buildings {
+                        building.track(2, 1);
+                    }
+                },
+            );
+        let building = app
+            .world_mut()
+            .spawn(ProgressComponent::<Loading>::default())
+            .id();
+
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::default());
+        assert_eq!(
+            app.world()
+                .get::<ProgressComponent<Loading>>(building)
+                .unwrap()
+                .count(),
+            ProgressCount::default()
+        );
+
+        app.world_mut().run_schedule(FinishLoading);
+        assert_eq!(testing::count(&app), ProgressCount::new(1, 2));
+    }
+}
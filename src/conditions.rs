@@ -0,0 +1,211 @@
+use std::any::type_name;
+
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::{Entity, EntityHashMap};
+use bevy::ecs::event::{Event, EventReader};
+use bevy::ecs::query::{Added, QueryData, QueryFilter};
+use bevy::ecs::removal_detection::RemovedComponents;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Local, Query, Res, ResMut};
+use bevy::ecs::world::World;
+
+use crate::{Progress, ProgressMarker, QueryCompletion, SharedProgress, TaskId};
+
+/// Returns a system tracking a task that is done once the condition returned `true`
+pub(crate) fn track_until<T, F>(mut condition: F) -> impl FnMut(&World)
+where
+    T: ProgressMarker,
+    F: FnMut(&World) -> bool + Send + Sync + 'static,
+{
+    let mut done = false;
+    move |world| {
+        done = done || condition(world);
+        world
+            .resource::<SharedProgress<T>>()
+            .track_count(done.into());
+    }
+}
+
+/// Returns a system tracking a task that is done once the query fulfilled the completion
+pub(crate) fn track_query_done<T, Q, F>(
+    completion: QueryCompletion,
+) -> impl FnMut(Query<Q, F>, ResMut<Progress<T>>)
+where
+    T: ProgressMarker,
+    Q: QueryData + 'static,
+    F: QueryFilter + 'static,
+{
+    let mut done = false;
+    move |query, mut progress| {
+        done = done
+            || match completion {
+                QueryCompletion::AnyMatch => !query.is_empty(),
+                QueryCompletion::NoMatch => query.is_empty(),
+            };
+        progress.track_source(type_name::<Query<Q, F>>(), done.into());
+    }
+}
+
+/// Returns a system tracking a task that is done once the resource `R` fulfilled the predicate
+pub(crate) fn track_resource_with<T, R, P>(
+    mut predicate: P,
+) -> impl FnMut(Option<Res<R>>, ResMut<Progress<T>>)
+where
+    T: ProgressMarker,
+    R: Resource,
+    P: FnMut(&R) -> bool + Send + Sync + 'static,
+{
+    let mut done = false;
+    move |resource, mut progress| {
+        done = done || resource.is_some_and(|resource| predicate(&resource));
+        progress.track_source(type_name::<R>(), done.into());
+    }
+}
+
+/// Returns a system tracking a task that is done once an event `E` fulfilled the predicate
+pub(crate) fn complete_on_event_with<T, E, P>(
+    mut predicate: P,
+) -> impl FnMut(EventReader<E>, ResMut<Progress<T>>)
+where
+    T: ProgressMarker,
+    E: Event,
+    P: FnMut(&E) -> bool + Send + Sync + 'static,
+{
+    let mut done = false;
+    move |mut events, mut progress| {
+        if !done {
+            done = events.read().any(&mut predicate);
+        } else {
+            events.clear();
+        }
+        progress.track_source(type_name::<E>(), done.into());
+    }
+}
+
+/// Tracks a named task for every entity with the component `C` that is done once the component is removed
+///
+/// Done tasks are removed once the tracker is ready, so the next loading session starts empty.
+pub(crate) fn complete_on_removed<T: ProgressMarker, C: Component>(
+    mut removed: RemovedComponents<C>,
+    added: Query<Entity, Added<C>>,
+    mut pending: Local<EntityHashMap<TaskId>>,
+    mut done: Local<Vec<TaskId>>,
+    mut progress: ResMut<Progress<T>>,
+) {
+    if progress.is_ready() {
+        for task in done.drain(..) {
+            progress.remove_named_task(task);
+        }
+    }
+    for entity in removed.read() {
+        if let Some(task) = pending.remove(&entity) {
+            progress.complete_named_task(task);
+            done.push(task);
+        }
+    }
+    for entity in &added {
+        pending
+            .entry(entity)
+            .or_insert_with(|| progress.add_named_task(type_name::<C>()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::entity::Entity;
+    use bevy::ecs::event::Event;
+    use bevy::ecs::query::With;
+
+    use crate::testing::{self, Loading, Pending, Ready};
+    use crate::{Progress, ProgressAppExt, ProgressCount, QueryCompletion};
+
+    #[derive(Event)]
+    struct Loaded(usize);
+
+    #[test]
+    fn track_until_latches_done() {
+        let mut app = testing::app();
+        app.insert_resource(Ready(false))
+            .track_until::<Loading, _>(|world| world.resource::<Ready>().0)
+            .track_until::<Loading, _>(|world| world.resource::<Ready>().0);
+
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::pending(2));
+
+        app.world_mut().resource_mut::<Ready>().0 = true;
+        app.update();
+        app.world_mut().resource_mut::<Ready>().0 = false;
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::done(2));
+    }
+
+    #[test]
+    fn track_query_done_waits_for_empty_query() {
+        let mut app = testing::app();
+        app.track_query_done::<Loading, Entity, With<Pending>>(QueryCompletion::NoMatch);
+        let entity = app.world_mut().spawn(Pending).id();
+
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::pending(1));
+
+        app.world_mut().despawn(entity);
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::done(1));
+    }
+
+    #[test]
+    fn track_resource_with_checks_predicate() {
+        let mut app = testing::app();
+        app.track_resource_with::<Loading, Ready, _>(|ready| ready.0);
+
+        app.update();
+        app.insert_resource(Ready(false));
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::pending(1));
+
+        app.insert_resource(Ready(true));
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::done(1));
+    }
+
+    #[test]
+    fn complete_on_event_with_filters_events() {
+        let mut app = testing::app();
+        app.complete_on_event_with::<Loading, Loaded, _>(|loaded| loaded.0 == 2);
+
+        app.world_mut().send_event(Loaded(1));
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::pending(1));
+
+        app.world_mut().send_event(Loaded(2));
+        app.update();
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::done(1));
+    }
+
+    #[test]
+    fn completes_tasks_on_removed_components() {
+        let mut app = testing::app();
+        app.complete_on_removed::<Loading, Pending>();
+        let first = app.world_mut().spawn(Pending).id();
+        let second = app.world_mut().spawn(Pending).id();
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::pending(2));
+
+        app.world_mut().entity_mut(first).remove::<Pending>();
+        app.world_mut().despawn(second);
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::done(2));
+
+        app.update();
+        let progress = app.world().resource::<Progress<Loading>>();
+        assert_eq!(progress.named_tasks().count(), 0);
+        assert!(progress.is_ready());
+
+        let third = app.world_mut().spawn(Pending).id();
+        app.update();
+        app.world_mut().despawn(third);
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::done(1));
+    }
+}
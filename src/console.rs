@@ -0,0 +1,192 @@
+use bevy::app::{App, Plugin};
+use bevy::ecs::system::SystemState;
+use bevy::ecs::world::World;
+use bevy_console::{AddConsoleCommand, ConsoleCommand};
+use clap::{Parser, Subcommand};
+
+use crate::{ProgressRegistry, RegisteredTracker};
+
+/// Plugin adding the `progress` command to the console of bevy_console
+///
+/// The command inspects and manipulates the trackers in the [`ProgressRegistry`] during development:
+/// - `progress list` prints the progress of every tracker
+/// - `progress show <marker>` prints the counts and pending [named tasks](crate::Progress::add_named_task) of a tracker
+/// - `progress clear <marker>` [clears](crate::Progress::clear) a tracker
+///
+/// Markers are given by their type name, with or without the module path.
+/// ```edition2021,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_console::ConsolePlugin;
+/// # use bevy_progress_tracking::{ProgressConsolePlugin, ProgressPlugin};
+/// # struct Loading;
+/// App::new().add_plugins((
+///     DefaultPlugins,
+///     ConsolePlugin,
+///     ProgressPlugin::<Loading>::new(),
+///     ProgressConsolePlugin,
+/// ));
+/// ```
+pub struct ProgressConsolePlugin;
+
+impl Plugin for ProgressConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command::<ProgressCommand, _>(progress_command);
+    }
+}
+
+/// Inspect and clear progress trackers
+#[derive(Parser, bevy_console::ConsoleCommand)]
+#[command(name = "progress")]
+struct ProgressCommand {
+    #[command(subcommand)]
+    action: ProgressAction,
+}
+
+#[derive(Subcommand)]
+enum ProgressAction {
+    /// List all trackers with their progress
+    List,
+    /// Show the counts and pending named tasks of a tracker
+    Show {
+        /// Type name of the marker
+        marker: String,
+    },
+    /// Clear all records of a tracker
+    Clear {
+        /// Type name of the marker
+        marker: String,
+    },
+}
+
+fn progress_command(world: &mut World, command: &mut SystemState<ConsoleCommand<ProgressCommand>>) {
+    let Some(Ok(ProgressCommand { action })) = command.get_mut(world).take() else {
+        return;
+    };
+    let reply = respond(action, world);
+    let mut command = command.get_mut(world);
+    match reply {
+        Ok(lines) => {
+            for line in lines {
+                command.reply(line);
+            }
+            command.ok();
+        }
+        Err(error) => command.reply_failed(error),
+    }
+}
+
+fn respond(action: ProgressAction, world: &mut World) -> Result<Vec<String>, String> {
+    let registry = world.get_resource::<ProgressRegistry>();
+    match action {
+        ProgressAction::List => Ok(registry
+            .into_iter()
+            .flat_map(ProgressRegistry::iter)
+            .filter_map(|tracker| {
+                let count = tracker.count(world)?;
+                let progress = tracker.ratio(world)?;
+                Some(format!(
+                    "{}: {}/{} ({:.0}%)",
+                    tracker.name(),
+                    count.done,
+                    count.total,
+                    progress * 100.
+                ))
+            })
+            .collect()),
+        ProgressAction::Show { marker } => {
+            let tracker = find(registry, &marker)?;
+            let (Some(count), Some(progress), Some(pending)) = (
+                tracker.count(world),
+                tracker.ratio(world),
+                tracker.pending_tasks(world),
+            ) else {
+                return Err(format!("The tracker {} was removed", tracker.name()));
+            };
+            let mut lines = vec![
+                tracker.name().to_owned(),
+                format!("done: {}/{}", count.done, count.total),
+                format!("progress: {:.0}%", progress * 100.),
+            ];
+            lines.extend(pending.into_iter().map(|label| format!("pending: {label}")));
+            Ok(lines)
+        }
+        ProgressAction::Clear { marker } => {
+            let tracker = find(registry, &marker)?;
+            tracker.clear(world);
+            Ok(vec![format!("Cleared {}", tracker.name())])
+        }
+    }
+}
+
+fn find(registry: Option<&ProgressRegistry>, marker: &str) -> Result<RegisteredTracker, String> {
+    registry
+        .into_iter()
+        .flat_map(ProgressRegistry::iter)
+        .find(|tracker| {
+            tracker.name() == marker || tracker.name().rsplit("::").next() == Some(marker)
+        })
+        .copied()
+        .ok_or_else(|| format!("No tracker with the marker {marker}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::App;
+    use bevy::ecs::event::Events;
+    use bevy_console::{ConsoleCommandEntered, ConsoleConfiguration, PrintConsoleLine};
+
+    use crate::testing::{self, Loading};
+    use crate::{Progress, ProgressConsolePlugin, ProgressCount};
+
+    fn run(app: &mut App, args: &[&str]) -> Vec<String> {
+        app.world_mut().send_event(ConsoleCommandEntered {
+            command_name: "progress".to_owned(),
+            args: args.iter().map(|arg| (*arg).to_owned()).collect(),
+        });
+        app.update();
+        let mut lines = app.world_mut().resource_mut::<Events<PrintConsoleLine>>();
+        lines.drain().map(|line| line.line).collect()
+    }
+
+    #[test]
+    fn inspects_and_clears_trackers() {
+        let mut app = testing::app();
+        app.init_resource::<ConsoleConfiguration>()
+            .add_event::<ConsoleCommandEntered>()
+            .add_event::<PrintConsoleLine>()
+            .add_plugins(ProgressConsolePlugin);
+        let mut progress = app.world_mut().resource_mut::<Progress<Loading>>();
+        progress.persist_tasks(2);
+        progress.persist_done(1);
+        progress.add_named_task("spawn player");
+        app.update();
+
+        let name = "bevy_progress_tracking::testing::Loading";
+        assert_eq!(
+            run(&mut app, &["list"]),
+            [&format!("{name}: 1/3 (33%)"), "[ok]"]
+        );
+        assert_eq!(
+            run(&mut app, &["show", "Loading"]),
+            [
+                name,
+                "done: 1/3",
+                "progress: 33%",
+                "pending: spawn player",
+                "[ok]"
+            ]
+        );
+        assert_eq!(
+            run(&mut app, &["show", "Saving"]),
+            ["No tracker with the marker Saving", "[failed]"]
+        );
+        assert_eq!(
+            run(&mut app, &["clear", name]),
+            [&format!("Cleared {name}"), "[ok]"]
+        );
+        assert_eq!(
+            app.world().resource::<Progress<Loading>>().count(),
+            ProgressCount::default()
+        );
+    }
+}
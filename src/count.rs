@@ -0,0 +1,242 @@
+use core::iter::{FromIterator, Sum};
+use core::ops::{Add, AddAssign};
+
+#[cfg(feature = "assets")]
+use bevy::asset::LoadState;
+
+use crate::Task;
+
+/// A number of done tasks out of a total number of tasks
+///
+/// This is the value type used by every part of the crate that reports or evaluates progress.
+/// Counts can be added together to combine the progress of multiple sources.
+/// ```edition2021
+/// # use bevy_progress_tracking::ProgressCount;
+/// let count = ProgressCount::done(2) + ProgressCount::pending(2);
+/// assert_eq!(count, ProgressCount::new(2, 4));
+/// assert_eq!(count.ratio(), 0.5);
+/// ```
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ProgressCount {
+    /// Number of done tasks
+    pub done: usize,
+    /// Total number of tasks, including the done ones
+    pub total: usize,
+}
+
+impl ProgressCount {
+    /// Create a count of `done` tasks out of `total` tasks
+    pub const fn new(done: usize, total: usize) -> Self {
+        ProgressCount { done, total }
+    }
+
+    /// Create a count of the given amount of tasks that are all done
+    pub const fn done(tasks: usize) -> Self {
+        ProgressCount::new(tasks, tasks)
+    }
+
+    /// Create a count of the given amount of tasks of which none are done yet
+    pub const fn pending(tasks: usize) -> Self {
+        ProgressCount::new(0, tasks)
+    }
+
+    /// Returns the ratio of done tasks as a floating point number between 0 and 1
+    ///
+    /// A count without any tasks is considered complete and has a ratio of 1.
+    pub fn ratio(&self) -> f32 {
+        crate::math::ratio(self.done, self.total)
+    }
+
+    /// Returns the ratio of done tasks in per mille, rounded down
+    ///
+    /// Unlike [`ProgressCount::ratio`], this only uses integer math and is guaranteed to be identical
+    /// on all platforms for the same counts.
+    /// ```edition2021
+    /// # use bevy_progress_tracking::ProgressCount;
+    /// assert_eq!(ProgressCount::new(1, 3).per_mille(), 333);
+    /// ```
+    pub fn per_mille(&self) -> u32 {
+        self.fixed_point(1000)
+    }
+
+    /// Returns the ratio of done tasks as Q16.16 fixed point number, rounded down
+    ///
+    /// A ratio of 1 corresponds to `1 << 16`. Like [`ProgressCount::per_mille`], the value is
+    /// guaranteed to be identical on all platforms for the same counts.
+    pub fn ratio_q16(&self) -> u32 {
+        self.fixed_point(1 << 16)
+    }
+
+    fn fixed_point(&self, one: u64) -> u32 {
+        let done = self.done.min(self.total) as u128;
+        (done * one as u128)
+            .checked_div(self.total as u128)
+            .map_or(one, |ratio| ratio as u64) as u32
+    }
+
+    /// Returns `true` if all tasks are done
+    pub fn is_complete(&self) -> bool {
+        self.done >= self.total
+    }
+
+    /// Scale the count to the given total, rounding the done tasks down
+    ///
+    /// ```edition2021
+    /// # use bevy_progress_tracking::ProgressCount;
+    /// assert_eq!(ProgressCount::new(2, 3).weighted(10), ProgressCount::new(6, 10));
+    /// ```
+    pub fn weighted(&self, weight: usize) -> ProgressCount {
+        // Computed in 128 bits, so large counts or weights cannot overflow
+        let done = (self.done.min(self.total) as u128 * weight as u128)
+            .checked_div(self.total as u128)
+            .map_or(weight, |done| done as usize);
+        ProgressCount::new(done, weight)
+    }
+
+    pub(crate) fn track(&mut self, tasks: usize, done: usize) {
+        self.total += tasks;
+        self.done += done;
+
+        debug_assert!(self.total >= self.done, "The last track call adding {} tasks and {} done tasks led to more done tasks than there are tasks", tasks, done);
+    }
+
+    pub(crate) fn task(&mut self, task: Task) {
+        if task == Task::Done {
+            self.track(1, 1);
+        } else {
+            self.track(1, 0);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.total = 0;
+        self.done = 0;
+    }
+}
+
+impl Add for ProgressCount {
+    type Output = ProgressCount;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        ProgressCount::new(self.done + rhs.done, self.total + rhs.total)
+    }
+}
+
+impl AddAssign for ProgressCount {
+    fn add_assign(&mut self, rhs: Self) {
+        self.done += rhs.done;
+        self.total += rhs.total;
+    }
+}
+
+impl Sum for ProgressCount {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(ProgressCount::default(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a ProgressCount> for ProgressCount {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl From<bool> for ProgressCount {
+    /// A single task that is done if the value is `true`
+    fn from(done: bool) -> Self {
+        if done {
+            ProgressCount::done(1)
+        } else {
+            ProgressCount::pending(1)
+        }
+    }
+}
+
+impl From<(usize, usize)> for ProgressCount {
+    /// Interpret the tuple as `(done, total)`
+    fn from((done, total): (usize, usize)) -> Self {
+        ProgressCount::new(done, total)
+    }
+}
+
+#[cfg(feature = "assets")]
+impl From<LoadState> for ProgressCount {
+    /// A single task that is done once the asset is loaded
+    ///
+    /// Assets that failed to load are never counted as done.
+    fn from(state: LoadState) -> Self {
+        matches!(state, LoadState::Loaded).into()
+    }
+}
+
+impl FromIterator<bool> for ProgressCount {
+    /// Count every item as one task that is done if the item is `true`
+    ///
+    /// ```edition2021
+    /// # use bevy_progress_tracking::ProgressCount;
+    /// let count: ProgressCount = [true, false, true].into_iter().collect();
+    /// assert_eq!(count, ProgressCount::new(2, 3));
+    /// ```
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        iter.into_iter().map(ProgressCount::from).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ProgressCount;
+    #[cfg(feature = "assets")]
+    use bevy::asset::LoadState;
+
+    #[test]
+    fn empty_count_is_complete() {
+        let count = ProgressCount::default();
+        assert!(count.is_complete());
+        assert_eq!(count.ratio(), 1.);
+    }
+
+    #[test]
+    fn sums_counts() {
+        let counts = [
+            ProgressCount::done(1),
+            ProgressCount::pending(2),
+            ProgressCount::new(1, 3),
+        ];
+        assert_eq!(
+            counts.iter().sum::<ProgressCount>(),
+            ProgressCount::new(2, 6)
+        );
+    }
+
+    #[test]
+    fn weights_large_counts() {
+        let count = ProgressCount::new(usize::MAX / 2, usize::MAX);
+        assert_eq!(count.weighted(1000), ProgressCount::new(499, 1000));
+        assert_eq!(
+            ProgressCount::new(1, 2).weighted(usize::MAX),
+            ProgressCount::new(usize::MAX / 2, usize::MAX)
+        );
+    }
+
+    #[cfg(feature = "assets")]
+    #[test]
+    fn converts_load_states() {
+        let count: ProgressCount = [LoadState::Loaded, LoadState::Loading, LoadState::NotLoaded]
+            .into_iter()
+            .map(ProgressCount::from)
+            .sum();
+        assert_eq!(count, ProgressCount::new(1, 3));
+    }
+
+    #[test]
+    fn fixed_point_ratios() {
+        assert_eq!(ProgressCount::default().per_mille(), 1000);
+        assert_eq!(ProgressCount::new(2, 3).per_mille(), 666);
+        assert_eq!(ProgressCount::new(1, 2).ratio_q16(), 1 << 15);
+        assert_eq!(ProgressCount::done(7).ratio_q16(), 1 << 16);
+        let large = ProgressCount::new(usize::MAX / 2, usize::MAX);
+        assert_eq!(large.per_mille(), 499);
+        assert_eq!(large.ratio_q16(), (1 << 15) - 1);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{Progress, ProgressCount};
+
+#[cfg_attr(
+    feature = "bevy",
+    doc = "Drives the per-frame lifecycle of a tracker outside of the [`ProgressPlugin`](crate::ProgressPlugin)"
+)]
+#[cfg_attr(
+    not(feature = "bevy"),
+    doc = "Drives the per-frame lifecycle of a tracker outside of the `ProgressPlugin`"
+)]
+///
+/// Use this to embed a tracker in custom runners, editors, or loops without Bevy.
+/// Every frame consists of the same steps the plugin runs:
+/// 1. [`begin`](ProgressDriver::begin) the frame and track progress in the returned [`Progress`]
+/// 2. [`collect`](ProgressDriver::collect) counts from other sources, like the plugin does with
+#[cfg_attr(feature = "bevy", doc = "   [`SharedProgress`](crate::SharedProgress)")]
+#[cfg_attr(not(feature = "bevy"), doc = "   `SharedProgress`")]
+/// 3. [`finish`](ProgressDriver::finish) the frame
+/// 4. [`evaluate`](ProgressDriver::evaluate) the counts of the finished frame
+/// ```edition2021
+/// # use bevy_progress_tracking::{ProgressCount, ProgressDriver, Task};
+/// struct Baking;
+///
+/// let mut driver = ProgressDriver::<Baking>::default();
+/// driver.begin().task(Task::Done);
+/// driver.collect(ProgressCount::pending(1));
+/// driver.finish();
+/// assert_eq!(driver.evaluate(), ProgressCount::new(1, 2));
+/// ```
+pub struct ProgressDriver<T> {
+    progress: Progress<T>,
+}
+
+impl<T> Default for ProgressDriver<T> {
+    fn default() -> Self {
+        ProgressDriver::new(Progress::default())
+    }
+}
+
+impl<T> ProgressDriver<T> {
+    /// Drive the given tracker
+    pub fn new(progress: Progress<T>) -> Self {
+        ProgressDriver { progress }
+    }
+
+    /// Begin a frame and return the tracker to report progress to
+    pub fn begin(&mut self) -> &mut Progress<T> {
+        &mut self.progress
+    }
+
+    /// Add counts that were collected outside of the tracker during this frame
+    pub fn collect(&mut self, count: ProgressCount) {
+        self.progress.track_shared(count);
+    }
+
+    /// Finish the frame
+    pub fn finish(&mut self) {
+        self.progress.finish_frame();
+    }
+
+    /// Returns the done and total tasks of the last finished frame
+    pub fn evaluate(&self) -> ProgressCount {
+        self.progress.count()
+    }
+
+    /// Returns the driven tracker
+    pub fn progress(&self) -> &Progress<T> {
+        &self.progress
+    }
+
+    /// Stop driving the tracker and return it
+    pub fn into_inner(self) -> Progress<T> {
+        self.progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ProgressCount, ProgressDriver};
+
+    struct Baking;
+
+    #[test]
+    fn counts_persisted_tasks_every_frame() {
+        let mut driver = ProgressDriver::<Baking>::default();
+        driver.begin().persist_tasks(2);
+        driver.finish();
+        assert_eq!(driver.evaluate(), ProgressCount::pending(2));
+
+        driver.begin().persist_done(1);
+        driver.collect(ProgressCount::done(1));
+        driver.finish();
+        assert_eq!(driver.evaluate(), ProgressCount::new(2, 3));
+        assert_eq!(driver.progress().contributions().len(), 2);
+    }
+}
@@ -0,0 +1,99 @@
+use bevy::asset::{AssetServer, UntypedHandle};
+use bevy::ecs::change_detection::DetectChanges;
+use bevy::ecs::system::{Local, Res, ResMut};
+use bevy::platform::collections::HashSet;
+use bevy_asset_loader::dynamic_asset::DynamicAssets;
+
+use crate::assets::is_finished;
+use crate::{ProgressMarker, TrackedAssets};
+
+/// Tracks the assets of every newly registered key of [`DynamicAssets`]
+///
+/// The handles are kept until the assets finished loading, so they are not dropped before
+/// the loading state of `bevy_asset_loader` requested them as well.
+pub(crate) fn track_dynamic_assets<T: ProgressMarker>(
+    dynamic_assets: Res<DynamicAssets>,
+    server: Res<AssetServer>,
+    mut tracked: ResMut<TrackedAssets<T>>,
+    mut keys: Local<HashSet<String>>,
+    mut loading: Local<Vec<UntypedHandle>>,
+) {
+    loading.retain(|handle| !is_finished(&server, handle.id()));
+    if !dynamic_assets.is_changed() {
+        return;
+    }
+    for (key, asset) in dynamic_assets.iter_assets() {
+        if keys.contains(key) {
+            continue;
+        }
+        keys.insert(key.to_owned());
+        for handle in asset.load(&server) {
+            tracked.track(&handle);
+            loading.push(handle);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::App;
+    use bevy::asset::io::AssetSourceBuilders;
+    use bevy::asset::{
+        Asset, AssetServer, AssetServerMode, Handle, UnapprovedPathMode, UntypedHandle,
+    };
+    use bevy::ecs::world::World;
+    use bevy::reflect::TypePath;
+    use bevy_asset_loader::dynamic_asset::{DynamicAsset, DynamicAssetType, DynamicAssets};
+
+    use crate::{ProgressAppExt, ProgressCount, ProgressPlugin, TrackedAssets};
+
+    struct Loading;
+
+    #[derive(Asset, TypePath)]
+    struct Level;
+
+    #[derive(Debug)]
+    struct Levels(usize);
+
+    impl DynamicAsset for Levels {
+        fn load(&self, _: &AssetServer) -> Vec<UntypedHandle> {
+            (0..self.0)
+                .map(|_| Handle::<Level>::default().untyped())
+                .collect()
+        }
+
+        fn build(&self, _: &mut World) -> Result<DynamicAssetType, anyhow::Error> {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn tracks_assets_of_new_keys() {
+        let mut sources = AssetSourceBuilders::default();
+        sources.init_default_source("assets", None);
+        let server = AssetServer::new(
+            sources.build_sources(false, false),
+            AssetServerMode::Unprocessed,
+            false,
+            UnapprovedPathMode::Forbid,
+        );
+        let mut app = App::new();
+        app.add_plugins(ProgressPlugin::<Loading>::new())
+            .insert_resource(server)
+            .init_resource::<DynamicAssets>()
+            .track_dynamic_assets::<Loading>();
+        app.world_mut()
+            .resource_mut::<DynamicAssets>()
+            .register_asset("levels", Box::new(Levels(2)));
+        app.update();
+        app.world_mut()
+            .resource_mut::<DynamicAssets>()
+            .register_asset("bonus", Box::new(Levels(1)));
+        app.update();
+        app.update();
+        assert_eq!(
+            app.world().resource::<TrackedAssets<Loading>>().count(),
+            ProgressCount::pending(3)
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Write the content to a temporary file next to the path and move it over the path
+///
+/// Readers never see a partially written file, even if the app is killed while writing.
+pub(crate) fn write_atomic(path: &Path, content: impl AsRef<[u8]>) -> io::Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    let mut file = File::create(&temporary)?;
+    file.write_all(content.as_ref())?;
+    file.sync_all()?;
+    fs::rename(&temporary, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::file::write_atomic;
+
+    #[test]
+    fn replaces_files_without_leaving_temporary_files() {
+        let path = std::env::temp_dir().join("bevy_progress_tracking_write_atomic");
+        write_atomic(&path, "first").unwrap();
+        write_atomic(&path, "second").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        assert!(!path.with_extension("tmp").exists());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,109 @@
+use std::any::type_name;
+
+use bevy::ecs::system::{Local, Res, ResMut};
+
+use crate::{Progress, ProgressCount, ProgressMarker};
+
+/// Returns a system adding the weighted progress of the tracker `Source` to the tracker `Target`
+pub(crate) fn forward<Source, Target>(
+    weight: usize,
+) -> impl FnMut(Res<Progress<Source>>, ResMut<Progress<Target>>)
+where
+    Source: ProgressMarker,
+    Target: ProgressMarker,
+{
+    move |source, mut target| {
+        target.track_source(type_name::<Source>(), source.count().weighted(weight));
+    }
+}
+
+/// Tracks the soft tasks of the tracker `T` that are left once it is ready in the tracker `Background`
+pub(crate) fn continue_in_background<T: ProgressMarker, Background: ProgressMarker>(
+    source: Res<Progress<T>>,
+    mut background: ResMut<Progress<Background>>,
+    mut done_when_ready: Local<Option<usize>>,
+) {
+    let soft = source.soft_count();
+    if !source.is_ready() {
+        *done_when_ready = None;
+    } else if done_when_ready.is_none() && source.count().total > 0 {
+        *done_when_ready = Some(soft.done);
+    }
+    if let Some(done) = *done_when_ready {
+        background.track_source(
+            type_name::<T>(),
+            ProgressCount::new(
+                soft.done.saturating_sub(done),
+                soft.total.saturating_sub(done),
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::Update;
+    use bevy::ecs::system::{Res, ResMut};
+
+    use crate::testing::{self, Loading, Ready};
+    use crate::{Progress, ProgressAppExt, ProgressCount, ProgressPlugin};
+
+    #[test]
+    fn forwards_weighted_progress() {
+        struct Plugin;
+
+        let mut app = testing::app();
+        app.add_plugins(ProgressPlugin::<Plugin>::new())
+            .forward::<Plugin, Loading>(10);
+        app.world_mut()
+            .resource_mut::<Progress<Plugin>>()
+            .persist_tasks(3);
+        app.world_mut()
+            .resource_mut::<Progress<Plugin>>()
+            .persist_done(2);
+
+        app.update();
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::new(6, 10));
+    }
+
+    #[test]
+    fn continues_soft_tasks_in_background() {
+        struct Background;
+
+        let mut app = testing::app();
+        app.add_plugins(ProgressPlugin::<Background>::new())
+            .continue_in_background::<Loading, Background>()
+            .insert_resource(Ready(false))
+            .add_systems(
+                Update,
+                |ready: Res<Ready>, mut progress: ResMut<Progress<Loading>>| {
+                    progress.track(1, ready.0 as usize);
+                    progress.track_soft(2, 1);
+                },
+            );
+
+        app.update();
+        app.update();
+        assert_eq!(
+            app.world().resource::<Progress<Background>>().count(),
+            ProgressCount::default()
+        );
+
+        app.world_mut().resource_mut::<Ready>().0 = true;
+        app.update();
+        app.update();
+        assert_eq!(
+            app.world().resource::<Progress<Background>>().count(),
+            ProgressCount::pending(1)
+        );
+
+        app.world_mut().resource_mut::<Ready>().0 = false;
+        app.update();
+        app.update();
+        assert_eq!(
+            app.world().resource::<Progress<Background>>().count(),
+            ProgressCount::default()
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use bevy::ecs::component::Component;
+use bevy::ecs::observer::Trigger;
+use bevy::ecs::system::{Commands, Query, ResMut};
+use bevy::ecs::world::{OnAdd, OnRemove};
+use bevy::render::gpu_readback::{Readback, ReadbackComplete};
+
+use crate::{Progress, ProgressMarker, TaskId};
+
+type ReadbackCheck = Box<dyn Fn(&[u8]) -> bool + Send + Sync>;
+
+/// Component tracking GPU work, like generating noise textures or lookup tables, as a named task of the tracker `T`
+///
+/// Spawn it together with a [`Readback`] of the buffer or texture the work writes into.
+/// The task is done once a read back of the data fulfills the completion check, for example a flag
+/// written at the end of a compute shader. Without a check, the first read back completes the task.
+/// The [`Readback`] is removed once the task is done, and despawning the entity before removes the task.
+/// Read backs are handled by the `GpuReadbackPlugin` of Bevy, which is part of the `DefaultPlugins`.
+/// Enable the tracking with [`ProgressAppExt::track_gpu_tasks`](crate::ProgressAppExt::track_gpu_tasks).
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy::render::gpu_readback::Readback;
+/// # use bevy::render::storage::ShaderStorageBuffer;
+/// # use bevy_progress_tracking::GpuTask;
+/// # struct Loading;
+/// #[derive(Resource)]
+/// struct NoiseBuffer(Handle<ShaderStorageBuffer>);
+///
+/// fn track_noise(mut commands: Commands, noise: Res<NoiseBuffer>) {
+///     commands.spawn((
+///         Readback::buffer(noise.0.clone()),
+///         // The compute shader writes a 1 into the last word once it is done
+///         GpuTask::<Loading>::new("noise texture").done_when(|data| data.ends_with(&1u32.to_le_bytes())),
+///     ));
+/// }
+/// ```
+#[derive(Component)]
+pub struct GpuTask<T> {
+    label: Cow<'static, str>,
+    is_done: Option<ReadbackCheck>,
+    task: Option<TaskId>,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> GpuTask<T> {
+    /// Track GPU work with the given label
+    pub fn new(label: impl Into<Cow<'static, str>>) -> Self {
+        GpuTask {
+            label: label.into(),
+            is_done: None,
+            task: None,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Only complete the task once the read back data fulfills the check
+    pub fn done_when(mut self, is_done: impl Fn(&[u8]) -> bool + Send + Sync + 'static) -> Self {
+        self.is_done = Some(Box::new(is_done));
+        self
+    }
+
+    /// Returns `true` once the work is done
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+pub(crate) fn start_gpu_task<T: ProgressMarker>(
+    trigger: Trigger<OnAdd, GpuTask<T>>,
+    mut tasks: Query<&mut GpuTask<T>>,
+    mut progress: ResMut<Progress<T>>,
+) {
+    if let Ok(mut task) = tasks.get_mut(trigger.target()) {
+        let label = task.label.clone();
+        task.task = Some(progress.add_named_task(label));
+    }
+}
+
+pub(crate) fn complete_gpu_task<T: ProgressMarker>(
+    trigger: Trigger<ReadbackComplete>,
+    mut commands: Commands,
+    mut tasks: Query<&mut GpuTask<T>>,
+    mut progress: ResMut<Progress<T>>,
+) {
+    let Ok(mut task) = tasks.get_mut(trigger.target()) else {
+        return;
+    };
+    if task.done
+        || !task
+            .is_done
+            .as_ref()
+            .is_none_or(|is_done| is_done(&trigger.event().0))
+    {
+        return;
+    }
+    task.done = true;
+    if let Some(id) = task.task {
+        progress.complete_named_task(id);
+    }
+    commands.entity(trigger.target()).remove::<Readback>();
+}
+
+pub(crate) fn remove_gpu_task<T: ProgressMarker>(
+    trigger: Trigger<OnRemove, GpuTask<T>>,
+    tasks: Query<&GpuTask<T>>,
+    mut progress: ResMut<Progress<T>>,
+) {
+    if let Ok(GpuTask {
+        task: Some(id),
+        done: false,
+        ..
+    }) = tasks.get(trigger.target())
+    {
+        progress.remove_named_task(*id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::asset::Handle;
+    use bevy::render::gpu_readback::{Readback, ReadbackComplete};
+    use bevy::render::storage::ShaderStorageBuffer;
+
+    use crate::testing::{self, Loading};
+    use crate::{GpuTask, ProgressAppExt, ProgressCount};
+
+    #[test]
+    fn completes_tasks_on_read_back() {
+        let mut app = testing::app();
+        app.track_gpu_tasks::<Loading>();
+        let noise = app
+            .world_mut()
+            .spawn((
+                Readback::buffer(Handle::<ShaderStorageBuffer>::default()),
+                GpuTask::<Loading>::new("noise").done_when(|data| data == [1]),
+            ))
+            .id();
+        let lookup = app
+            .world_mut()
+            .spawn(GpuTask::<Loading>::new("lookup"))
+            .id();
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::pending(2));
+
+        app.world_mut()
+            .trigger_targets(ReadbackComplete(vec![0]), noise);
+        app.world_mut().despawn(lookup);
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::pending(1));
+
+        app.world_mut()
+            .trigger_targets(ReadbackComplete(vec![1]), noise);
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::done(1));
+        let noise = app.world().entity(noise);
+        assert!(noise.get::<GpuTask<Loading>>().unwrap().is_done());
+        assert!(!noise.contains::<Readback>());
+    }
+}
@@ -0,0 +1,156 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::event::{Event, EventWriter};
+use bevy::ecs::system::{Query, Res, ResMut};
+use bevy::time::Time;
+
+use crate::{Progress, ProgressCount, ProgressMarker};
+
+/// Component of a job that takes a fixed duration, like crafting an item or constructing a building
+///
+/// All timed jobs of the tracker `T` are reported to [`Progress<T>`] as the source `"timed jobs"`
+/// once they are tracked with [`ProgressAppExt::track_timed_jobs`](crate::ProgressAppExt::track_timed_jobs).
+/// Every job counts with its duration in milliseconds, so longer jobs weigh more.
+/// A [`TimedJobCompleted<T>`] event is sent when a job is done.
+/// ```edition2021
+/// # use std::time::Duration;
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::TimedJob;
+/// struct Crafting;
+///
+/// fn craft_sword(mut commands: Commands, time: Res<Time>) {
+///     commands.spawn(TimedJob::<Crafting>::start(Duration::from_secs(3), &time));
+/// }
+/// ```
+#[derive(Component)]
+pub struct TimedJob<T: ProgressMarker> {
+    /// How long the job takes
+    pub duration: Duration,
+    /// Elapsed [`Time`] when the job started
+    pub started_at: Duration,
+    completed: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: ProgressMarker> TimedJob<T> {
+    /// Create a job with the given duration that started at the given elapsed time
+    pub fn new(duration: Duration, started_at: Duration) -> Self {
+        TimedJob {
+            duration,
+            started_at,
+            completed: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a job with the given duration that starts now
+    pub fn start<C: Default>(duration: Duration, time: &Time<C>) -> Self {
+        TimedJob::new(duration, time.elapsed())
+    }
+
+    /// Returns the done and total milliseconds of the job at the given elapsed time
+    pub fn count(&self, elapsed: Duration) -> ProgressCount {
+        let total = self.duration.as_millis() as usize;
+        let done = elapsed.saturating_sub(self.started_at).as_millis() as usize;
+        ProgressCount::new(done.min(total), total)
+    }
+
+    /// Returns the time left until the job is done at the given elapsed time
+    pub fn remaining(&self, elapsed: Duration) -> Duration {
+        (self.started_at + self.duration).saturating_sub(elapsed)
+    }
+}
+
+/// Event sent once the [`TimedJob<T>`] of the entity is done
+#[derive(Event)]
+pub struct TimedJobCompleted<T> {
+    /// Entity of the completed job
+    pub entity: Entity,
+    _marker: PhantomData<fn() -> T>,
+}
+
+pub(crate) fn track_timed_jobs<T: ProgressMarker>(
+    mut jobs: Query<(Entity, &mut TimedJob<T>)>,
+    time: Res<Time>,
+    mut progress: ResMut<Progress<T>>,
+    mut completed: EventWriter<TimedJobCompleted<T>>,
+) {
+    let mut count = ProgressCount::default();
+    for (entity, mut job) in jobs.iter_mut() {
+        let job_count = job.count(time.elapsed());
+        count += job_count;
+        if !job.completed && job_count.is_complete() {
+            job.completed = true;
+            completed.write(TimedJobCompleted {
+                entity,
+                _marker: PhantomData,
+            });
+        }
+    }
+    if count != ProgressCount::default() {
+        progress.track_unchecked_source("timed jobs", count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::event::Events;
+    use bevy::time::{Time, TimePlugin, TimeUpdateStrategy};
+
+    use crate::testing::{self, Loading};
+    use crate::{ProgressAppExt, ProgressCount, TimedJob, TimedJobCompleted};
+
+    struct Crafting;
+
+    #[test]
+    fn counts_elapsed_milliseconds() {
+        let job = TimedJob::<Crafting>::new(Duration::from_secs(2), Duration::from_secs(1));
+        assert_eq!(job.count(Duration::ZERO), ProgressCount::pending(2000));
+        assert_eq!(
+            job.count(Duration::from_millis(1500)),
+            ProgressCount::new(500, 2000)
+        );
+        assert_eq!(job.count(Duration::from_secs(5)), ProgressCount::done(2000));
+        assert_eq!(
+            job.remaining(Duration::from_secs(2)),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn tracks_timed_jobs() {
+        let mut app = testing::app();
+        app.add_plugins(TimePlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .track_timed_jobs::<Loading>();
+        app.update();
+        let elapsed = app.world().resource::<Time>().elapsed();
+        app.world_mut().spawn(TimedJob::<Loading>::new(
+            Duration::from_millis(200),
+            elapsed,
+        ));
+
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::new(100, 200));
+        assert!(app
+            .world()
+            .resource::<Events<TimedJobCompleted<Loading>>>()
+            .is_empty());
+
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::done(200));
+        assert_eq!(
+            app.world()
+                .resource::<Events<TimedJobCompleted<Loading>>>()
+                .len(),
+            1
+        );
+    }
+}
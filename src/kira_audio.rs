@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::Res;
+use bevy_kira_audio::{AudioChannel, AudioControl};
+
+use crate::{Progress, ProgressMarker};
+
+/// Returns a system crossfading from the `L` to the `G` audio channel when the tracker `T` is done
+///
+/// The `L` channel is paused over `fade_out` and the `G` channel resumed over `fade_in` in the first frame
+/// the tracker tracked tasks and is ready in. In the first frame it tracks tasks that are not done,
+/// the `G` channel is paused over `fade_out` and the `L` channel resumed over `fade_in`.
+#[allow(clippy::type_complexity)]
+pub(crate) fn crossfade_channels<T: ProgressMarker, L: Resource, G: Resource>(
+    fade_out: Duration,
+    fade_in: Duration,
+) -> impl FnMut(Res<Progress<T>>, Res<AudioChannel<L>>, Res<AudioChannel<G>>) {
+    let mut was_ready = None;
+    move |progress, loading, gameplay| {
+        // Without tasks, the tracker is neither loading nor done
+        let ready = (progress.count().total > 0).then(|| progress.is_ready());
+        if ready.is_none() || was_ready == ready {
+            return;
+        }
+        was_ready = ready;
+        if ready == Some(true) {
+            crossfade(&*loading, &*gameplay, fade_out, fade_in);
+        } else {
+            crossfade(&*gameplay, &*loading, fade_out, fade_in);
+        }
+    }
+}
+
+fn crossfade(
+    silent: &impl AudioControl,
+    audible: &impl AudioControl,
+    fade_out: Duration,
+    fade_in: Duration,
+) {
+    silent.pause().linear_fade_out(fade_out);
+    audible.resume().linear_fade_in(fade_in);
+}
@@ -3,168 +3,196 @@
 //! This little library can be used to track any kind of tasks. The most prominent example
 //! would be asset loading, but you could also use it to keep track of necessary preparation steps
 //! like world generation, or in-game tasks.
+//!
+//! The tracking itself ([`Progress`] and [`ProgressCount`]) does not depend on Bevy. Without the default
+//! `bevy` feature, the crate is `no_std` and can be used in server tools or asset pipelines.
+//! Tracking assets of the asset server requires the default `assets` feature. Without it, the Bevy integration
+//! only depends on the core parts of Bevy.
 
 #![forbid(unsafe_code)]
 #![warn(unused_imports, missing_docs)]
-
-#[derive(Clone, Default, Debug, PartialEq)]
-struct TaskProgress {
-    tasks: usize,
-    done: usize,
-}
-
-/// Resource that keeps record of current, previous and persisted progress
-#[derive(Default, Debug)]
-pub struct Progress {
-    current: TaskProgress,
-    previous: TaskProgress,
-    persisted: TaskProgress,
-}
-
-/// Convenience enum to mark a single task as `in progress` or `done`
-#[derive(PartialEq)]
-pub enum Task {
-    /// Mark a task as done
-    Done,
-    /// Mark a task as in progress
-    InProgress,
-}
-
-impl TaskProgress {
-    fn track(&mut self, tasks: usize, done: usize) {
-        self.tasks += tasks;
-        self.done += done;
-
-        debug_assert!(self.tasks >= self.done, "The last track call adding {} tasks and {} done tasks led to more done tasks than there are tasks", tasks, done);
-    }
-
-    fn task(&mut self, task: Task) {
-        if task == Task::Done {
-            self.track(1, 1);
-        } else {
-            self.track(1, 0);
-        }
-    }
-
-    fn clear(&mut self) {
-        self.tasks = 0;
-        self.done = 0;
-    }
-}
-
-impl Progress {
-    /// track the given amount of tasks of wich some can already be completed
-    pub fn track(&mut self, tasks: usize, done: usize) {
-        self.current.tasks += tasks;
-        self.current.done += done;
-    }
-
-    /// Stop progress tracking for the given frame and clear the current count for the next frame
-    ///
-    /// This function should be called every frame before the progress is evaluated by calling [Progress::progress]
-    pub fn finish_frame(&mut self) {
-        self.track(self.persisted.tasks, self.persisted.done);
-        self.previous = self.current.clone();
-        self.current.clear();
-    }
-
-    /// Convenience function to track a single task
-    ///
-    /// ```edition2021
-    /// # use bevy_progress_tracking::{Progress, Task};
-    /// # let mut progress = Progress::default();
-    /// progress.task(Task::Done);
-    /// ```
-    /// is the equivalent of `progress.track(1, 1)`.
-    ///
-    ///
-    /// ```edition2021
-    /// # use bevy_progress_tracking::{Progress, Task};
-    /// # let mut progress = Progress::default();
-    /// progress.task(Task::InProgress);
-    /// ```
-    /// is the equivalent of `progress.track(1, 0)`.
-    pub fn task(&mut self, task: Task) {
-        self.current.task(task);
-    }
-
-    /// Returns the progress as a floating point number between 0 and 1
-    ///
-    /// The values are taken from the last finished frame.
-    /// You probably want to call [ProgressTracker::finish_frame] before calling this function.
-    pub fn progress(&self) -> f32 {
-        (self.previous.done as f32 / self.previous.tasks as f32).min(1.0)
-    }
-
-    /// Persist the given amount of tasks and mark them all as done
-    ///
-    /// Running the following once:
-    /// ```edition2021
-    /// # use bevy_progress_tracking::Progress;
-    /// # let mut progress = Progress::default();
-    /// progress.persist_done_tasks(42);
-    /// ```
-    /// is the equivalent of calling `progress.track(42, 42)` in *every* frame.
-    pub fn persist_done_tasks(&mut self, done: usize) {
-        self.persisted.track(done, done);
-    }
-
-    /// Persist the given amount of done tasks
-    ///
-    /// Running the following once:
-    /// ```edition2021
-    /// # use bevy_progress_tracking::Progress;
-    /// # let mut progress = Progress::default();
-    /// # progress.persist_tasks(42);
-    /// progress.persist_done(42);
-    /// ```
-    /// is the equivalent of calling `progress.track(0, 42)` in every later *every* frame.
-    pub fn persist_done(&mut self, done: usize) {
-        self.persisted.track(0, done);
-    }
-
-    /// Persist the given amount of tasks
-    ///
-    /// Running the following once:
-    /// ```edition2021
-    /// # use bevy_progress_tracking::Progress;
-    /// # let mut progress = Progress::default();
-    /// progress.persist_tasks(42);
-    /// ```
-    /// is the equivalent of calling `progress.track(42, 0)` in every later *every* frame.
-    pub fn persist_tasks(&mut self, tasks: usize) {
-        self.persisted.track(tasks, 0);
-    }
-
-    /// Clear the progress resource
-    ///
-    /// This effectively resets all records
-    pub fn clear(&mut self) {
-        self.current.clear();
-        self.previous.clear();
-        self.persisted.clear();
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{Progress, TaskProgress};
-
-    #[test]
-    fn correctly_tracks_persistent_tasks() {
-        let mut progress = Progress::default();
-        progress.persist_tasks(3);
-        progress.persist_done(1);
-        progress.persist_done_tasks(3);
-        assert_eq!(progress.current, TaskProgress::default());
-        assert_eq!(progress.previous, TaskProgress::default());
-
-        progress.finish_frame();
-        assert_eq!(progress.progress(), 4. / 6.);
-        assert_eq!(progress.current, TaskProgress::default());
-        assert_eq!(progress.previous, TaskProgress { tasks: 6, done: 4 });
-
-        progress.finish_frame();
-        assert_eq!(progress.progress(), 4. / 6.);
-    }
-}
+#![cfg_attr(not(any(feature = "bevy", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "bevy")]
+mod app;
+#[cfg(feature = "assets")]
+mod assets;
+#[cfg(feature = "atlas")]
+mod atlas;
+#[cfg(feature = "audio")]
+mod audio;
+#[cfg(feature = "billboard")]
+mod billboard;
+mod bounded;
+#[cfg(feature = "bevy")]
+mod budget;
+mod calculator;
+#[cfg(feature = "bevy")]
+mod checkpoint;
+#[cfg(feature = "bevy")]
+mod cleanup;
+#[cfg(feature = "bevy")]
+mod clock;
+#[cfg(feature = "bevy")]
+mod component;
+#[cfg(feature = "bevy")]
+mod conditions;
+#[cfg(feature = "console")]
+mod console;
+mod count;
+mod driver;
+#[cfg(feature = "asset_loader")]
+mod dynamic_assets;
+#[cfg(feature = "bevy")]
+mod file;
+#[cfg(feature = "bevy")]
+mod forward;
+#[cfg(feature = "gpu")]
+mod gpu;
+#[cfg(feature = "bevy")]
+mod jobs;
+#[cfg(feature = "kira_audio")]
+mod kira_audio;
+#[cfg(feature = "bevy")]
+mod map;
+mod marker;
+pub mod math;
+mod named;
+#[cfg(feature = "bevy")]
+mod objectives;
+#[cfg(feature = "bevy")]
+mod owner;
+#[cfg(feature = "assets")]
+mod packages;
+#[cfg(feature = "perf")]
+mod perf;
+#[cfg(feature = "bevy")]
+mod plugin;
+#[cfg(feature = "bevy")]
+mod pressure;
+mod progress;
+#[cfg(feature = "bevy")]
+mod provider;
+#[cfg(feature = "bevy")]
+mod registry;
+#[cfg(feature = "assets")]
+mod reload;
+#[cfg(feature = "remote")]
+mod remote;
+#[cfg(feature = "bevy")]
+mod replay;
+#[cfg(feature = "bevy")]
+mod report;
+#[cfg(feature = "bevy")]
+mod shared;
+#[cfg(feature = "bevy")]
+mod spans;
+#[cfg(feature = "bevy")]
+mod startup;
+#[cfg(feature = "persist_store")]
+mod store;
+#[cfg(feature = "terminal")]
+mod terminal;
+#[cfg(all(test, feature = "bevy"))]
+mod testing;
+#[cfg(feature = "text")]
+mod text;
+#[cfg(feature = "bevy")]
+mod thresholds;
+#[cfg(feature = "bevy")]
+mod tips;
+#[cfg(feature = "bevy")]
+mod view;
+#[cfg(feature = "bevy")]
+mod warm_up;
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+mod web;
+#[cfg(feature = "bevy")]
+mod worlds;
+
+#[cfg(feature = "bevy")]
+pub use app::{ProgressAppExt, QueryCompletion};
+#[cfg(feature = "assets")]
+pub use assets::TrackedAssets;
+#[cfg(feature = "atlas")]
+pub use atlas::{AtlasJobId, AtlasWeight, BuiltAtlas, TextureAtlasJobs};
+#[cfg(feature = "audio")]
+pub use audio::ProgressCrossfade;
+#[cfg(feature = "billboard")]
+pub use billboard::{ProgressBillboard, ProgressBillboardPlugin};
+pub use bounded::BoundedValueProgress;
+#[cfg(feature = "bevy")]
+pub use budget::{ProgressBudget, ProgressBudgetPlugin};
+pub use calculator::{ProgressCalculator, ProgressCurve};
+#[cfg(feature = "bevy")]
+pub use checkpoint::{CheckpointJob, ProgressCheckpoint, ResumableJob, ResumeJobPlugin};
+#[cfg(feature = "bevy")]
+pub use cleanup::{DespawnOnProgressComplete, DespawnOnProgressStart};
+#[cfg(feature = "bevy")]
+pub use clock::ProgressClock;
+#[cfg(feature = "bevy")]
+pub use component::{ProgressComponent, ProgressComponentPlugin};
+#[cfg(feature = "console")]
+pub use console::ProgressConsolePlugin;
+pub use count::ProgressCount;
+pub use driver::ProgressDriver;
+#[cfg(feature = "gpu")]
+pub use gpu::GpuTask;
+#[cfg(feature = "bevy")]
+pub use jobs::{TimedJob, TimedJobCompleted};
+#[cfg(feature = "bevy")]
+pub use map::{ProgressMap, ProgressMapPlugin};
+pub use marker::ProgressMarker;
+pub use named::{GroupCompletion, GroupId, TaskId};
+#[cfg(feature = "bevy")]
+pub use objectives::{ObjectiveCompleted, Objectives};
+#[cfg(feature = "bevy")]
+pub use owner::{OwnedTasks, OwnerDespawned, OwnerPolicy};
+#[cfg(feature = "assets")]
+pub use packages::{ManifestError, PackageManifest};
+#[cfg(feature = "perf")]
+pub use perf::ProgressPerf;
+#[cfg(feature = "bevy")]
+pub use plugin::{ProgressPlugin, ProgressSystems};
+#[cfg(feature = "bevy")]
+pub use pressure::ProgressPressure;
+pub use progress::{PersistScope, Progress, Task, TotalRegression};
+#[cfg(feature = "bevy")]
+pub use provider::ProgressProvider;
+#[cfg(feature = "bevy")]
+pub use registry::{ProgressRegistry, RegisteredTracker};
+#[cfg(feature = "assets")]
+pub use reload::AssetReload;
+#[cfg(feature = "remote")]
+pub use remote::{ProgressRemotePlugin, BRP_PROGRESS_METHOD};
+#[cfg(feature = "bevy")]
+pub use replay::{ProgressRecording, RecordProgressPlugin, ReplayProgressPlugin};
+#[cfg(feature = "bevy")]
+pub use report::{
+    resident_memory, LoadingSessionReport, SessionRegression, SessionReportPlugin, TimelineEntry,
+    TimingDiff,
+};
+#[cfg(feature = "bevy")]
+pub use shared::SharedProgress;
+#[cfg(feature = "bevy")]
+pub use spans::ProgressSpansPlugin;
+#[cfg(feature = "bevy")]
+pub use startup::{AppStartup, PluginTimings, StartupProgressPlugin};
+#[cfg(feature = "persist_store")]
+pub use store::{ProgressStore, ProgressStorePlugin};
+#[cfg(feature = "terminal")]
+pub use terminal::TerminalProgressPlugin;
+#[cfg(feature = "text")]
+pub use text::GlyphWarmUp;
+#[cfg(feature = "bevy")]
+pub use tips::LoadingTips;
+#[cfg(feature = "bevy")]
+pub use view::ProgressView;
+#[cfg(feature = "bevy")]
+pub use warm_up::WarmUp;
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+pub use web::PROGRESS_EVENT;
+#[cfg(feature = "bevy")]
+pub use worlds::WorldsProgress;
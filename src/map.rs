@@ -0,0 +1,141 @@
+use std::borrow::Cow;
+
+use bevy::app::{App, Last, Plugin};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::ResMut;
+
+use crate::Progress;
+
+/// Resource of trackers that are created at runtime and keyed by name
+///
+/// Use it when the trackers are not known at compile time, like one tracker per loaded mod.
+/// Every tracker is a [`Progress`] with the same per-frame semantics as a tracker with a marker type.
+/// Their frames are finished in [`Last`] by the [`ProgressMapPlugin`].
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{ProgressMap, ProgressMapPlugin};
+/// fn load_mods(mut trackers: ResMut<ProgressMap>) {
+///     trackers.tracker_mut("dragons").track(3, 1);
+/// }
+///
+/// App::new()
+///     .add_plugins(ProgressMapPlugin)
+///     .add_systems(Update, load_mods);
+/// ```
+#[derive(Resource, Default, Debug)]
+pub struct ProgressMap {
+    trackers: Vec<(Cow<'static, str>, Progress<ProgressMap>)>,
+}
+
+impl ProgressMap {
+    /// Returns the tracker with the given name
+    pub fn get(&self, name: &str) -> Option<&Progress<ProgressMap>> {
+        self.trackers
+            .iter()
+            .find(|(tracker, _)| tracker == name)
+            .map(|(_, progress)| progress)
+    }
+
+    /// Returns the tracker with the given name, creating it if it does not exist
+    pub fn tracker_mut(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> &mut Progress<ProgressMap> {
+        let name = name.into();
+        let index = match self
+            .trackers
+            .iter()
+            .position(|(tracker, _)| *tracker == name)
+        {
+            Some(index) => index,
+            None => {
+                self.trackers.push((name, Progress::default()));
+                self.trackers.len() - 1
+            }
+        };
+        &mut self.trackers[index].1
+    }
+
+    /// Remove the tracker with the given name
+    pub fn remove(&mut self, name: &str) -> Option<Progress<ProgressMap>> {
+        let index = self
+            .trackers
+            .iter()
+            .position(|(tracker, _)| tracker == name)?;
+        Some(self.trackers.remove(index).1)
+    }
+
+    /// Iterate over all trackers with their names in the order they were created
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Progress<ProgressMap>)> {
+        self.trackers
+            .iter()
+            .map(|(name, progress)| (name.as_ref(), progress))
+    }
+
+    /// Finish the frame of every tracker
+    ///
+    /// See [`Progress::finish_frame`].
+    pub fn finish_frame(&mut self) {
+        for (_, progress) in self.trackers.iter_mut() {
+            progress.finish_frame();
+        }
+    }
+}
+
+/// Plugin managing the [`ProgressMap`] resource
+///
+/// The frames of all trackers in the map are finished at the end of every frame in [`Last`].
+pub struct ProgressMapPlugin;
+
+impl Plugin for ProgressMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProgressMap>()
+            .add_systems(Last, |mut trackers: ResMut<ProgressMap>| {
+                trackers.finish_frame()
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, Update};
+    use bevy::ecs::system::ResMut;
+
+    use crate::{ProgressCount, ProgressMap, ProgressMapPlugin};
+
+    #[test]
+    fn finishes_frames_of_dynamic_trackers() {
+        let mut app = App::new();
+        app.add_plugins(ProgressMapPlugin).add_systems(
+            Update,
+            |mut trackers: ResMut<ProgressMap>| {
+                trackers.tracker_mut("dragons").track(2, 1);
+                trackers.tracker_mut(String::from("castles")).track(1, 1);
+            },
+        );
+        app.update();
+
+        let trackers = app.world().resource::<ProgressMap>();
+        let counts: Vec<_> = trackers
+            .iter()
+            .map(|(name, progress)| (name, progress.count()))
+            .collect();
+        assert_eq!(
+            counts,
+            [
+                ("dragons", ProgressCount::new(1, 2)),
+                ("castles", ProgressCount::done(1))
+            ]
+        );
+        assert!(trackers.get("castles").unwrap().count().is_complete());
+
+        app.world_mut()
+            .resource_mut::<ProgressMap>()
+            .remove("dragons");
+        assert!(app
+            .world()
+            .resource::<ProgressMap>()
+            .get("dragons")
+            .is_none());
+    }
+}
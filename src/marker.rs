@@ -0,0 +1,25 @@
+/// Marker type distinguishing trackers in the same app
+///
+/// Every type that is `Send + Sync + 'static` is a marker, usually a unit struct like `struct Loading;`.
+/// There is no need to implement this trait; it exists to name the requirements in bounds.
+/// A type that does not qualify is reported as missing `Send`, `Sync` or `'static` where it is used.
+///
+/// There is no derive for this trait. It is implemented for all qualifying types, so a derived
+/// implementation would conflict with it, and requiring a derive would break every existing marker.
+/// ```edition2021
+/// # use bevy_progress_tracking::ProgressMarker;
+/// struct Loading;
+///
+/// fn assert_marker<T: ProgressMarker>() {}
+/// assert_marker::<Loading>();
+/// ```
+/// Types holding data that is not thread safe are no markers:
+/// ```compile_fail
+/// # use std::rc::Rc;
+/// # use bevy_progress_tracking::ProgressMarker;
+/// # fn assert_marker<T: ProgressMarker>() {}
+/// assert_marker::<Rc<()>>();
+/// ```
+pub trait ProgressMarker: Send + Sync + 'static {}
+
+impl<T: Send + Sync + 'static> ProgressMarker for T {}
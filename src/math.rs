@@ -0,0 +1,84 @@
+//! The math used to turn counts into progress
+//!
+//! These are the functions the trackers use internally. Custom displays can use them
+//! to get the same numbers as the plugin.
+
+/// Returns the ratio of done tasks as a floating point number between 0 and 1
+///
+/// Without any tasks, the ratio is 1. This is what [`ProgressCount::ratio`](crate::ProgressCount::ratio) returns.
+/// ```edition2021
+/// # use bevy_progress_tracking::math::ratio;
+/// assert_eq!(ratio(1, 4), 0.25);
+/// assert_eq!(ratio(0, 0), 1.);
+/// ```
+pub fn ratio(done: usize, total: usize) -> f32 {
+    if total == 0 {
+        return 1.0;
+    }
+    (done as f32 / total as f32).min(1.0)
+}
+
+/// Merge ratios by their weights
+///
+/// Every ratio is clamped between 0 and 1 first. Without any weight, the merged ratio is 1
+/// like for a tracker without tasks.
+/// ```edition2021
+/// # use bevy_progress_tracking::math::weighted_merge;
+/// assert_eq!(weighted_merge([(1., 1.), (0.5, 2.)]), 2. / 3.);
+/// ```
+pub fn weighted_merge(ratios: impl IntoIterator<Item = (f32, f32)>) -> f32 {
+    let (sum, weights) = ratios
+        .into_iter()
+        .filter(|(_, weight)| *weight > 0.)
+        .fold((0., 0.), |(sum, weights), (ratio, weight)| {
+            (sum + ratio.clamp(0., 1.) * weight, weights + weight)
+        });
+    if weights == 0. {
+        return 1.0;
+    }
+    sum / weights
+}
+
+/// Map the ratio of a phase to its range of the overall progress
+///
+/// A phase from `0.25` to `0.75` at half of its progress results in `0.5`.
+/// ```edition2021
+/// # use bevy_progress_tracking::math::map_to_phase;
+/// assert_eq!(map_to_phase(0.5, 0.25, 0.75), 0.5);
+/// ```
+pub fn map_to_phase(ratio: f32, start: f32, end: f32) -> f32 {
+    start + ratio.clamp(0., 1.) * (end - start)
+}
+
+/// Returns the current ratio, but never less than the previous one
+///
+/// Use this to keep displayed progress from going backwards when new tasks are discovered.
+/// ```edition2021
+/// # use bevy_progress_tracking::math::monotonic;
+/// assert_eq!(monotonic(0.6, 0.4), 0.6);
+/// assert_eq!(monotonic(0.6, 0.8), 0.8);
+/// ```
+pub fn monotonic(previous: f32, current: f32) -> f32 {
+    previous.max(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::{map_to_phase, monotonic, ratio, weighted_merge};
+
+    #[test]
+    fn merges_weighted_ratios() {
+        assert_eq!(weighted_merge([]), 1.);
+        assert_eq!(weighted_merge([(0.5, 0.)]), 1.);
+        assert_eq!(weighted_merge([(2., 1.), (0., 1.)]), 0.5);
+        assert_eq!(weighted_merge([(0.25, 3.), (1., 1.)]), 0.4375);
+    }
+
+    #[test]
+    fn maps_and_clamps_ratios() {
+        assert_eq!(ratio(3, 2), 1.);
+        assert_eq!(map_to_phase(1.5, 0.5, 1.), 1.);
+        assert_eq!(map_to_phase(0., 0.5, 1.), 0.5);
+        assert_eq!(monotonic(0.5, 0.5), 0.5);
+    }
+}
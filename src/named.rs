@@ -0,0 +1,317 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::ProgressCount;
+
+/// Identifier of a named task registered with [`Progress::add_named_task`](crate::Progress::add_named_task)
+///
+/// Ids of removed tasks are never valid again, even if their storage is reused by a new task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TaskId {
+    index: u32,
+    generation: u32,
+}
+
+/// Identifier of a group of named tasks registered with [`Progress::add_task_group`](crate::Progress::add_task_group)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GroupId(u32);
+
+/// When a group of named tasks is complete
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum GroupCompletion {
+    /// The group is complete once all of its tasks are done
+    All,
+    /// The group is complete once any of its tasks is done
+    ///
+    /// The remaining tasks of a complete group are counted as done.
+    Any,
+}
+
+struct TaskGroup {
+    completion: GroupCompletion,
+    members: Vec<TaskId>,
+}
+
+struct NamedTask {
+    label: Cow<'static, str>,
+    done: bool,
+    /// Not done, but member of a complete [`GroupCompletion::Any`] group
+    satisfied: bool,
+}
+
+#[derive(Default)]
+struct Slot {
+    generation: u32,
+    task: Option<NamedTask>,
+}
+
+/// Slab of named tasks
+///
+/// Storage of removed tasks is reused, so adding and removing many tasks does not allocate
+/// once the slab grew large enough. Static labels are never copied.
+#[derive(Default)]
+pub(crate) struct NamedTasks {
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+    count: ProgressCount,
+    groups: Vec<Option<TaskGroup>>,
+    satisfied: usize,
+}
+
+impl NamedTasks {
+    pub(crate) fn add(&mut self, label: Cow<'static, str>) -> TaskId {
+        let index = self.free.pop().unwrap_or_else(|| {
+            self.slots.push(Slot::default());
+            (self.slots.len() - 1) as u32
+        });
+        let slot = &mut self.slots[index as usize];
+        slot.task = Some(NamedTask {
+            label,
+            done: false,
+            satisfied: false,
+        });
+        self.count.track(1, 0);
+        TaskId {
+            index,
+            generation: slot.generation,
+        }
+    }
+
+    pub(crate) fn get(&self, id: TaskId) -> Option<(&str, bool)> {
+        task(&self.slots, id).map(|task| (task.label.as_ref(), task.done))
+    }
+
+    pub(crate) fn complete(&mut self, id: TaskId) -> bool {
+        let Some(task) = task_mut(&mut self.slots, id) else {
+            return false;
+        };
+        if !task.done {
+            task.done = true;
+            self.count.track(0, 1);
+            self.update_satisfied();
+        }
+        true
+    }
+
+    pub(crate) fn remove(&mut self, id: TaskId) -> bool {
+        let Some(slot) = self
+            .slots
+            .get_mut(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)
+        else {
+            return false;
+        };
+        let Some(task) = slot.task.take() else {
+            return false;
+        };
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(id.index);
+        self.count.total -= 1;
+        if task.done {
+            self.count.done -= 1;
+        }
+        self.update_satisfied();
+        true
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (TaskId, &str, bool)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            slot.task.as_ref().map(|task| {
+                (
+                    TaskId {
+                        index: index as u32,
+                        generation: slot.generation,
+                    },
+                    task.label.as_ref(),
+                    task.done,
+                )
+            })
+        })
+    }
+
+    /// Returns the count of all tasks, with the remaining tasks of complete [`GroupCompletion::Any`] groups done
+    pub(crate) fn count(&self) -> ProgressCount {
+        let mut count = self.count;
+        count.track(0, self.satisfied);
+        count
+    }
+
+    /// Flag the pending members of complete [`GroupCompletion::Any`] groups after tasks or groups changed
+    fn update_satisfied(&mut self) {
+        let NamedTasks {
+            slots,
+            groups,
+            satisfied,
+            ..
+        } = self;
+        for group in groups.iter().flatten() {
+            for &member in &group.members {
+                if let Some(task) = task_mut(slots, member) {
+                    task.satisfied = false;
+                }
+            }
+        }
+        *satisfied = 0;
+        for group in groups.iter().flatten() {
+            if group.completion != GroupCompletion::Any || !is_complete(slots, group) {
+                continue;
+            }
+            for &member in &group.members {
+                if let Some(task) =
+                    task_mut(slots, member).filter(|task| !task.done && !task.satisfied)
+                {
+                    task.satisfied = true;
+                    *satisfied += 1;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn add_group(
+        &mut self,
+        completion: GroupCompletion,
+        members: Vec<TaskId>,
+    ) -> GroupId {
+        self.groups.push(Some(TaskGroup {
+            completion,
+            members,
+        }));
+        self.update_satisfied();
+        GroupId((self.groups.len() - 1) as u32)
+    }
+
+    pub(crate) fn remove_group(&mut self, id: GroupId) -> bool {
+        let Some(group) = self.groups.get_mut(id.0 as usize).and_then(Option::take) else {
+            return false;
+        };
+        for &member in &group.members {
+            if let Some(task) = task_mut(&mut self.slots, member) {
+                task.satisfied = false;
+            }
+        }
+        self.update_satisfied();
+        true
+    }
+
+    /// Returns the done and total tasks of the group
+    ///
+    /// Tasks that were removed are no longer part of the group.
+    pub(crate) fn group(&self, id: GroupId) -> Option<ProgressCount> {
+        let group = self.groups.get(id.0 as usize)?.as_ref()?;
+        let members: ProgressCount = group
+            .members
+            .iter()
+            .filter_map(|&member| self.get(member))
+            .map(|(_, done)| ProgressCount::from(done))
+            .sum();
+        if group.completion == GroupCompletion::Any && is_complete(&self.slots, group) {
+            return Some(ProgressCount::done(members.total));
+        }
+        Some(members)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if slot.task.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free.push(index as u32);
+            }
+        }
+        self.count.clear();
+        self.satisfied = 0;
+        // Keep the slots, so ids of cleared groups do not refer to new groups
+        self.groups.iter_mut().for_each(|group| *group = None);
+    }
+}
+
+fn task(slots: &[Slot], id: TaskId) -> Option<&NamedTask> {
+    slots
+        .get(id.index as usize)
+        .filter(|slot| slot.generation == id.generation)
+        .and_then(|slot| slot.task.as_ref())
+}
+
+fn task_mut(slots: &mut [Slot], id: TaskId) -> Option<&mut NamedTask> {
+    slots
+        .get_mut(id.index as usize)
+        .filter(|slot| slot.generation == id.generation)
+        .and_then(|slot| slot.task.as_mut())
+}
+
+fn is_complete(slots: &[Slot], group: &TaskGroup) -> bool {
+    let mut members = group
+        .members
+        .iter()
+        .filter_map(|&member| task(slots, member))
+        .peekable();
+    if members.peek().is_none() {
+        return true;
+    }
+    match group.completion {
+        GroupCompletion::All => members.all(|task| task.done),
+        GroupCompletion::Any => members.any(|task| task.done),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::named::{GroupCompletion, NamedTasks};
+    use crate::ProgressCount;
+
+    #[test]
+    fn reuses_slots_without_reviving_ids() {
+        let mut tasks = NamedTasks::default();
+        let first = tasks.add("first".into());
+        tasks.complete(first);
+        assert_eq!(tasks.count(), ProgressCount::done(1));
+
+        assert!(tasks.remove(first));
+        let second = tasks.add("second".into());
+        assert_eq!(tasks.slots.len(), 1);
+        assert!(!tasks.complete(first));
+        assert_eq!(tasks.get(second), Some(("second", false)));
+        assert_eq!(tasks.count(), ProgressCount::pending(1));
+    }
+
+    #[test]
+    fn any_group_completes_remaining_tasks() {
+        let mut tasks = NamedTasks::default();
+        let cached = tasks.add("load cached world".into());
+        let generated = tasks.add("generate world".into());
+        let spawned = tasks.add("spawn player".into());
+        let world = tasks.add_group(GroupCompletion::Any, vec![cached, generated]);
+        let all = tasks.add_group(GroupCompletion::All, vec![cached, spawned]);
+        assert_eq!(tasks.count(), ProgressCount::pending(3));
+
+        tasks.complete(generated);
+        assert_eq!(tasks.group(world), Some(ProgressCount::done(2)));
+        assert_eq!(tasks.group(all), Some(ProgressCount::pending(2)));
+        assert_eq!(tasks.count(), ProgressCount::new(2, 3));
+
+        tasks.remove(cached);
+        assert_eq!(tasks.group(world), Some(ProgressCount::done(1)));
+        assert!(tasks.remove_group(world));
+        assert_eq!(tasks.group(world), None);
+        assert_eq!(tasks.count(), ProgressCount::new(1, 2));
+    }
+
+    #[test]
+    fn counts_members_of_overlapping_any_groups_once() {
+        let mut tasks = NamedTasks::default();
+        let first = tasks.add("first".into());
+        let second = tasks.add("second".into());
+        let shared = tasks.add("shared".into());
+        let left = tasks.add_group(GroupCompletion::Any, vec![first, shared]);
+        tasks.add_group(GroupCompletion::Any, vec![second, shared]);
+
+        tasks.complete(first);
+        tasks.complete(second);
+        assert_eq!(tasks.count(), ProgressCount::done(3));
+
+        tasks.remove_group(left);
+        assert_eq!(tasks.count(), ProgressCount::done(3));
+        tasks.remove(second);
+        assert_eq!(tasks.count(), ProgressCount::new(1, 2));
+    }
+}
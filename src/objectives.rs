@@ -0,0 +1,308 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use bevy::ecs::change_detection::DetectChangesMut;
+use bevy::ecs::component::Component;
+use bevy::ecs::event::{Event, EventReader, EventWriter};
+use bevy::ecs::query::Added;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Query, ResMut};
+
+use crate::{Progress, ProgressCount, ProgressMarker};
+
+/// Resource of gameplay objectives tracked as part of the tracker `T`
+///
+/// Every objective has a label and a target count, like "collect 10 gems".
+/// Objectives are reported to [`Progress<T>`] as the source `"objectives"`, so the tracker holds the
+/// overall progress of all objectives. Completing an objective sends an [`ObjectiveCompleted<T>`] event.
+/// Add the resource with [`ProgressAppExt::track_objectives`](crate::ProgressAppExt::track_objectives).
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::Objectives;
+/// # struct Quest;
+/// # #[derive(Component)]
+/// # struct Gem;
+/// fn collect_gems(
+///     mut commands: Commands,
+///     gems: Query<Entity, With<Gem>>,
+///     mut objectives: ResMut<Objectives<Quest>>,
+/// ) {
+///     for gem in &gems {
+///         commands.entity(gem).despawn();
+///         objectives.advance("collect gems", 1);
+///     }
+/// }
+/// ```
+#[derive(Resource)]
+pub struct Objectives<T> {
+    objectives: Vec<Objective>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+struct Objective {
+    label: Cow<'static, str>,
+    count: ProgressCount,
+    announced: bool,
+}
+
+impl<T> Default for Objectives<T> {
+    fn default() -> Self {
+        Objectives {
+            objectives: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Marker resource for apps that already report the objectives of the tracker `T`
+#[derive(Resource)]
+pub(crate) struct ObjectivesReported<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for ObjectivesReported<T> {
+    fn default() -> Self {
+        ObjectivesReported(PhantomData)
+    }
+}
+
+/// Event sent once an objective of [`Objectives<T>`] reached its target
+#[derive(Event)]
+pub struct ObjectiveCompleted<T> {
+    /// Label of the completed objective
+    pub label: Cow<'static, str>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Objectives<T> {
+    /// Define an objective with the given target count
+    ///
+    /// Defining an existing objective again changes its target and keeps its progress.
+    pub fn define(&mut self, label: impl Into<Cow<'static, str>>, target: usize) {
+        let label = label.into();
+        match self.find_mut(&label) {
+            Some(objective) => {
+                objective.count.total = target;
+                objective.count.done = objective.count.done.min(target);
+                objective.announced &= objective.count.is_complete();
+            }
+            None => self.objectives.push(Objective {
+                label,
+                count: ProgressCount::pending(target),
+                announced: false,
+            }),
+        }
+    }
+
+    /// Advance the objective by the given amount
+    ///
+    /// Progress beyond the target is ignored. Returns `false` if the objective does not exist.
+    pub fn advance(&mut self, label: &str, amount: usize) -> bool {
+        let Some(objective) = self.find_mut(label) else {
+            return false;
+        };
+        objective.count.done = (objective.count.done + amount).min(objective.count.total);
+        true
+    }
+
+    /// Set the progress of the objective
+    ///
+    /// Returns `false` if the objective does not exist.
+    pub fn set(&mut self, label: &str, done: usize) -> bool {
+        let Some(objective) = self.find_mut(label) else {
+            return false;
+        };
+        objective.count.done = done.min(objective.count.total);
+        objective.announced &= objective.count.is_complete();
+        true
+    }
+
+    /// Remove the objective
+    ///
+    /// Returns `false` if the objective does not exist.
+    pub fn remove(&mut self, label: &str) -> bool {
+        let len = self.objectives.len();
+        self.objectives.retain(|objective| objective.label != label);
+        self.objectives.len() != len
+    }
+
+    /// Returns the progress of the objective
+    pub fn get(&self, label: &str) -> Option<ProgressCount> {
+        self.objectives
+            .iter()
+            .find(|objective| objective.label == label)
+            .map(|objective| objective.count)
+    }
+
+    /// Iterate over all objectives with their label and progress
+    pub fn iter(&self) -> impl Iterator<Item = (&str, ProgressCount)> {
+        self.objectives
+            .iter()
+            .map(|objective| (objective.label.as_ref(), objective.count))
+    }
+
+    /// Returns the overall progress of all objectives
+    ///
+    /// Objectives are weighted by their target count. Use [`Objectives::completed`] to count
+    /// every objective the same.
+    pub fn count(&self) -> ProgressCount {
+        self.objectives
+            .iter()
+            .map(|objective| objective.count)
+            .sum()
+    }
+
+    /// Returns the number of completed objectives out of all objectives
+    pub fn completed(&self) -> ProgressCount {
+        self.objectives
+            .iter()
+            .map(|objective| objective.count.is_complete())
+            .collect()
+    }
+
+    /// Define an objective with the given progress that does not send a completion event
+    #[cfg(feature = "persist_store")]
+    pub(crate) fn restore(&mut self, label: impl Into<Cow<'static, str>>, count: ProgressCount) {
+        let label = label.into();
+        self.define(label.clone(), count.total);
+        if let Some(objective) = self.find_mut(&label) {
+            objective.count.done = count.done.min(count.total);
+            objective.announced = objective.count.is_complete();
+        }
+    }
+
+    fn find_mut(&mut self, label: &str) -> Option<&mut Objective> {
+        self.objectives
+            .iter_mut()
+            .find(|objective| objective.label == label)
+    }
+}
+
+pub(crate) fn report_objectives<T: ProgressMarker>(
+    mut objectives: ResMut<Objectives<T>>,
+    mut progress: ResMut<Progress<T>>,
+    mut completed: EventWriter<ObjectiveCompleted<T>>,
+) {
+    let count = objectives.count();
+    if count != ProgressCount::default() {
+        progress.track_unchecked_source("objectives", count);
+    }
+    // Announcing completions is not a change of the objectives
+    let objectives = objectives.bypass_change_detection();
+    for objective in objectives.objectives.iter_mut() {
+        if !objective.announced && objective.count.is_complete() {
+            objective.announced = true;
+            completed.write(ObjectiveCompleted {
+                label: objective.label.clone(),
+                _marker: PhantomData,
+            });
+        }
+    }
+}
+
+/// Returns a system advancing the objective with the label once for every event `E`
+pub(crate) fn advance_on_event<T: ProgressMarker, E: Event>(
+    label: &'static str,
+) -> impl FnMut(EventReader<E>, ResMut<Objectives<T>>) {
+    move |mut events, mut objectives| {
+        let amount = events.read().count();
+        if amount > 0 {
+            objectives.advance(label, amount);
+        }
+    }
+}
+
+/// Returns a system advancing the objective with the label once for every added component `C`
+pub(crate) fn advance_on_added<T: ProgressMarker, C: Component>(
+    label: &'static str,
+) -> impl FnMut(Query<(), Added<C>>, ResMut<Objectives<T>>) {
+    move |added, mut objectives| {
+        let amount = added.iter().count();
+        if amount > 0 {
+            objectives.advance(label, amount);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::Last;
+    use bevy::ecs::event::{Event, EventReader, Events};
+    use bevy::ecs::resource::Resource;
+    use bevy::ecs::system::ResMut;
+
+    use crate::testing::{self, Loading};
+    use crate::{ObjectiveCompleted, Objectives, ProgressAppExt, ProgressCount};
+
+    struct Quest;
+
+    #[derive(Event)]
+    struct Loaded;
+
+    #[test]
+    fn clamps_objectives_to_target() {
+        let mut objectives = Objectives::<Quest>::default();
+        objectives.define("gems", 10);
+        objectives.define("boss", 1);
+        assert!(objectives.advance("gems", 12));
+        assert!(!objectives.advance("coins", 1));
+        assert_eq!(objectives.get("gems"), Some(ProgressCount::done(10)));
+        assert_eq!(objectives.count(), ProgressCount::new(10, 11));
+        assert_eq!(objectives.completed(), ProgressCount::new(1, 2));
+
+        objectives.define("gems", 5);
+        assert_eq!(objectives.get("gems"), Some(ProgressCount::done(5)));
+    }
+
+    #[test]
+    fn advances_objectives_on_events() {
+        #[derive(Resource, Default)]
+        struct Completed(Vec<String>);
+
+        let mut app = testing::app();
+        app.advance_objective_on_event::<Loading, Loaded>("loaded")
+            .init_resource::<Completed>()
+            .add_systems(
+                Last,
+                |mut events: EventReader<ObjectiveCompleted<Loading>>,
+                 mut completed: ResMut<Completed>| {
+                    completed
+                        .0
+                        .extend(events.read().map(|event| event.label.to_string()));
+                },
+            );
+        app.world_mut()
+            .resource_mut::<Objectives<Loading>>()
+            .define("loaded", 2);
+
+        app.world_mut().send_event(Loaded);
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::new(1, 2));
+
+        app.world_mut().send_event(Loaded);
+        app.world_mut().send_event(Loaded);
+        app.update();
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::done(2));
+        assert_eq!(app.world().resource::<Completed>().0, ["loaded"]);
+    }
+
+    #[test]
+    fn reports_objectives_inserted_before_tracking() {
+        let mut objectives = Objectives::<Loading>::default();
+        objectives.define("loaded", 1);
+        objectives.advance("loaded", 1);
+
+        let mut app = testing::app();
+        app.insert_resource(objectives)
+            .track_objectives::<Loading>()
+            .track_objectives::<Loading>();
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::done(1));
+        assert_eq!(
+            app.world()
+                .resource::<Events<ObjectiveCompleted<Loading>>>()
+                .iter_current_update_events()
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use std::marker::PhantomData;
+
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::event::{Event, EventWriter};
+use bevy::ecs::observer::Trigger;
+use bevy::ecs::system::{Query, ResMut};
+use bevy::ecs::world::OnRemove;
+
+use crate::{Progress, ProgressMarker, TaskId};
+
+/// Component linking named tasks of the tracker `T` to the entity it is on
+///
+/// When the entity is despawned or the component is removed, the tasks that are not done yet are
+/// resolved according to the [`OwnerPolicy`] and an [`OwnerDespawned`] event is sent.
+/// Without this, a tracker would never complete when an entity doing work for it is despawned mid-load.
+/// Ownership is handled by the [`ProgressPlugin`](crate::ProgressPlugin).
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{OwnedTasks, OwnerPolicy, Progress};
+/// # struct Loading;
+/// fn spawn_npc(mut commands: Commands, mut progress: ResMut<Progress<Loading>>) {
+///     let pathfinding = progress.add_named_task("npc pathfinding");
+///     commands.spawn(OwnedTasks::<Loading>::new(OwnerPolicy::Cancel).with_task(pathfinding));
+/// }
+/// ```
+#[derive(Component)]
+pub struct OwnedTasks<T: ProgressMarker> {
+    tasks: Vec<TaskId>,
+    policy: OwnerPolicy,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// What happens to the owned tasks that are not done when their owner is despawned
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OwnerPolicy {
+    /// Remove the tasks from the tracker
+    #[default]
+    Cancel,
+    /// Mark the tasks as done
+    Complete,
+}
+
+impl<T: ProgressMarker> OwnedTasks<T> {
+    /// Create a component without tasks
+    pub fn new(policy: OwnerPolicy) -> Self {
+        OwnedTasks {
+            tasks: Vec::new(),
+            policy,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Own the given task
+    pub fn with_task(mut self, task: TaskId) -> Self {
+        self.push(task);
+        self
+    }
+
+    /// Own the given task
+    pub fn push(&mut self, task: TaskId) {
+        self.tasks.push(task);
+    }
+
+    /// Returns all owned tasks
+    pub fn tasks(&self) -> &[TaskId] {
+        &self.tasks
+    }
+
+    /// Returns the policy for tasks that are not done when the owner is despawned
+    pub fn policy(&self) -> OwnerPolicy {
+        self.policy
+    }
+}
+
+/// Event sent when an entity with [`OwnedTasks`] is despawned or the component is removed
+#[derive(Event, Debug)]
+pub struct OwnerDespawned<T> {
+    /// The entity that owned the tasks
+    pub entity: Entity,
+    /// Owned tasks that were not done and got resolved by the policy
+    pub tasks: Vec<TaskId>,
+    /// The policy that was applied to the tasks
+    pub policy: OwnerPolicy,
+    _marker: PhantomData<fn() -> T>,
+}
+
+pub(crate) fn release_owned_tasks<T: ProgressMarker>(
+    trigger: Trigger<OnRemove, OwnedTasks<T>>,
+    owners: Query<&OwnedTasks<T>>,
+    mut progress: ResMut<Progress<T>>,
+    mut despawned: EventWriter<OwnerDespawned<T>>,
+) {
+    let entity = trigger.target();
+    let Ok(owned) = owners.get(entity) else {
+        return;
+    };
+    let tasks: Vec<_> = owned
+        .tasks
+        .iter()
+        .copied()
+        .filter(|task| progress.named_task(*task).is_some_and(|(_, done)| !done))
+        .collect();
+    for task in &tasks {
+        match owned.policy {
+            OwnerPolicy::Cancel => progress.remove_named_task(*task),
+            OwnerPolicy::Complete => progress.complete_named_task(*task),
+        };
+    }
+    despawned.write(OwnerDespawned {
+        entity,
+        tasks,
+        policy: owned.policy,
+        _marker: PhantomData,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::App;
+    use bevy::ecs::event::Events;
+
+    use crate::{OwnedTasks, OwnerDespawned, OwnerPolicy, Progress, ProgressCount, ProgressPlugin};
+
+    struct Loading;
+
+    #[test]
+    fn resolves_owned_tasks_on_despawn() {
+        let mut app = App::new();
+        app.add_plugins(ProgressPlugin::<Loading>::new());
+        let mut progress = app.world_mut().resource_mut::<Progress<Loading>>();
+        let pathfinding = progress.add_named_task("pathfinding");
+        let animation = progress.add_named_task("animation");
+        let done = progress.add_named_task("spawned");
+        progress.complete_named_task(done);
+        let cancelled = app
+            .world_mut()
+            .spawn(OwnedTasks::<Loading>::new(OwnerPolicy::Cancel).with_task(pathfinding))
+            .id();
+        let completed = app
+            .world_mut()
+            .spawn(
+                OwnedTasks::<Loading>::new(OwnerPolicy::Complete)
+                    .with_task(animation)
+                    .with_task(done),
+            )
+            .id();
+
+        app.world_mut().despawn(cancelled);
+        app.world_mut().despawn(completed);
+        let events = app.world().resource::<Events<OwnerDespawned<Loading>>>();
+        let released: Vec<_> = events
+            .iter_current_update_events()
+            .map(|event| (event.entity, event.tasks.clone()))
+            .collect();
+        assert_eq!(
+            released,
+            [(cancelled, vec![pathfinding]), (completed, vec![animation])]
+        );
+
+        app.update();
+        assert_eq!(
+            app.world().resource::<Progress<Loading>>().count(),
+            ProgressCount::done(2)
+        );
+    }
+}
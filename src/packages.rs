@@ -0,0 +1,499 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::asset::{AssetServer, UntypedAssetId, UntypedHandle};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Res, ResMut};
+
+use crate::assets::{asset_load, AssetLoad};
+use crate::{Progress, ProgressCount, ProgressMarker};
+
+/// Resource of content packages, like mods or DLCs, loading as part of the tracker `T`
+///
+/// Every package consists of assets and init steps. Its assets are loaded once the packages are
+/// tracked with [`ProgressAppExt::track_packages`](crate::ProgressAppExt::track_packages),
+/// and init steps are completed by the systems that do the work. The manifest keeps all loaded assets alive.
+/// All packages are reported to [`Progress<T>`] as the source `"packages"`, while the progress of
+/// every single package is available through [`PackageManifest::package`].
+///
+/// Manifests can be parsed from a list of packages. Every package starts with its name in brackets,
+/// followed by the paths of its assets on separate lines. Lines starting with `#` are ignored.
+/// ```edition2021
+/// # use bevy_progress_tracking::PackageManifest;
+/// # struct Loading;
+/// let mut manifest = PackageManifest::<Loading>::parse(
+///     "[castles]
+///     castles/keep.glb
+///     castles/walls.glb
+///     # optional content
+///     [dragons]
+///     dragons/red.glb",
+/// )
+/// .unwrap();
+/// manifest.add_step("dragons", "register dragon behaviour");
+/// assert_eq!(manifest.packages().count(), 2);
+/// ```
+#[derive(Resource)]
+pub struct PackageManifest<T> {
+    packages: Vec<Package>,
+    load_limit: Option<usize>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+struct Package {
+    name: Cow<'static, str>,
+    unloaded: Vec<String>,
+    assets: Vec<UntypedHandle>,
+    finished: usize,
+    failed: usize,
+    load_limit: Option<usize>,
+    steps: Vec<(Cow<'static, str>, bool)>,
+}
+
+impl Package {
+    fn count(&self) -> ProgressCount {
+        let assets = ProgressCount::new(self.finished, self.unloaded.len() + self.assets.len());
+        assets + self.steps.iter().map(|(_, done)| *done).collect()
+    }
+
+    fn loading(&self) -> usize {
+        self.assets.len() - self.finished
+    }
+
+    fn check_loaded(&mut self, state: &mut impl FnMut(UntypedAssetId) -> AssetLoad) {
+        // Finished assets are moved to the front
+        for index in self.finished..self.assets.len() {
+            let state = state(self.assets[index].id());
+            if state != AssetLoad::Loading {
+                self.assets.swap(self.finished, index);
+                self.finished += 1;
+                self.failed += (state == AssetLoad::Failed) as usize;
+            }
+        }
+    }
+}
+
+impl<T> Default for PackageManifest<T> {
+    fn default() -> Self {
+        PackageManifest {
+            packages: Vec::new(),
+            load_limit: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Error of parsing a [`PackageManifest`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ManifestError {
+    /// Line of the manifest that could not be parsed, starting at 1
+    pub line: usize,
+}
+
+impl Display for ManifestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "asset path in line {} does not belong to a package",
+            self.line
+        )
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl<T> PackageManifest<T> {
+    /// Parse a list of packages and their asset paths
+    pub fn parse(manifest: &str) -> Result<Self, ManifestError> {
+        let mut parsed = PackageManifest::default();
+        for (index, line) in manifest.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                parsed.add_package(name.trim().to_owned());
+                continue;
+            }
+            let Some(package) = parsed.packages.last_mut() else {
+                return Err(ManifestError { line: index + 1 });
+            };
+            package.unloaded.push(line.to_owned());
+        }
+        Ok(parsed)
+    }
+
+    /// Add an empty package
+    ///
+    /// Does nothing if a package with the same name exists.
+    pub fn add_package(&mut self, name: impl Into<Cow<'static, str>>) {
+        let name = name.into();
+        if self.find_mut(&name).is_none() {
+            self.packages.push(Package {
+                name,
+                unloaded: Vec::new(),
+                assets: Vec::new(),
+                finished: 0,
+                failed: 0,
+                load_limit: None,
+                steps: Vec::new(),
+            });
+        }
+    }
+
+    /// Add the path of an asset to load as part of the package
+    ///
+    /// Returns `false` if the package does not exist.
+    pub fn add_asset(&mut self, package: &str, path: impl Into<String>) -> bool {
+        let Some(package) = self.find_mut(package) else {
+            return false;
+        };
+        package.unloaded.push(path.into());
+        true
+    }
+
+    /// Limit how many assets of all packages are loading at the same time
+    ///
+    /// Further assets are queued until loads finish or fail. This avoids thrashing slow storage or connections.
+    /// Without a limit, all assets are requested at once. A limit of zero would never load anything,
+    /// so it is rejected by returning `false` and keeping the previous limit.
+    pub fn set_load_limit(&mut self, limit: Option<usize>) -> bool {
+        if limit == Some(0) {
+            return false;
+        }
+        self.load_limit = limit;
+        true
+    }
+
+    /// Limit how many assets of the package are loading at the same time
+    ///
+    /// The limit of the whole manifest still applies. Returns `false` if the package does not exist
+    /// or the limit is zero.
+    pub fn set_package_load_limit(&mut self, package: &str, limit: Option<usize>) -> bool {
+        if limit == Some(0) {
+            return false;
+        }
+        let Some(package) = self.find_mut(package) else {
+            return false;
+        };
+        package.load_limit = limit;
+        true
+    }
+
+    /// Keep only the queued assets for which the predicate returns `true`
+    ///
+    /// Queued assets are not requested from the asset server yet, because the manifest was just added or
+    /// a [load limit](PackageManifest::set_load_limit) applies. The predicate gets the name of the package
+    /// and can change the asset path, for example to load a lower quality variant instead.
+    pub fn retain_queued(&mut self, mut predicate: impl FnMut(&str, &mut String) -> bool) {
+        for package in self.packages.iter_mut() {
+            package
+                .unloaded
+                .retain_mut(|path| predicate(&package.name, path));
+        }
+    }
+
+    /// Add an init step that is in progress until it is completed with [`PackageManifest::complete_step`]
+    ///
+    /// Returns `false` if the package does not exist.
+    pub fn add_step(&mut self, package: &str, step: impl Into<Cow<'static, str>>) -> bool {
+        let Some(package) = self.find_mut(package) else {
+            return false;
+        };
+        package.steps.push((step.into(), false));
+        true
+    }
+
+    /// Mark the init step of the package as done
+    ///
+    /// Returns `false` if the package or the step does not exist.
+    pub fn complete_step(&mut self, package: &str, step: &str) -> bool {
+        let Some((_, done)) = self
+            .find_mut(package)
+            .and_then(|package| package.steps.iter_mut().find(|(name, _)| name == step))
+        else {
+            return false;
+        };
+        *done = true;
+        true
+    }
+
+    /// Returns the number of assets of the package that failed to load
+    ///
+    /// Failed assets count as done in the progress of the package, so a broken asset does not block loading.
+    pub fn failed_assets(&self, name: &str) -> Option<usize> {
+        self.packages
+            .iter()
+            .find(|package| package.name == name)
+            .map(|package| package.failed)
+    }
+
+    /// Returns the progress of the package
+    ///
+    /// Loaded assets are updated every frame in `Update`. Assets that failed to load count as done,
+    /// see [`PackageManifest::failed_assets`].
+    pub fn package(&self, name: &str) -> Option<ProgressCount> {
+        self.packages
+            .iter()
+            .find(|package| package.name == name)
+            .map(Package::count)
+    }
+
+    /// Iterate over all packages with their name and progress
+    pub fn packages(&self) -> impl Iterator<Item = (&str, ProgressCount)> {
+        self.packages
+            .iter()
+            .map(|package| (package.name.as_ref(), package.count()))
+    }
+
+    /// Returns the progress of all packages
+    pub fn count(&self) -> ProgressCount {
+        self.packages.iter().map(Package::count).sum()
+    }
+
+    fn find_mut(&mut self, name: &str) -> Option<&mut Package> {
+        self.packages
+            .iter_mut()
+            .find(|package| package.name == name)
+    }
+
+    fn update(
+        &mut self,
+        mut load: impl FnMut(String) -> UntypedHandle,
+        mut state: impl FnMut(UntypedAssetId) -> AssetLoad,
+    ) {
+        for package in self.packages.iter_mut() {
+            package.check_loaded(&mut state);
+        }
+        let loading: usize = self.packages.iter().map(Package::loading).sum();
+        let mut available = self
+            .load_limit
+            .map_or(usize::MAX, |limit| limit.saturating_sub(loading));
+        for package in self.packages.iter_mut() {
+            let loading = package.loading();
+            let requests = package
+                .load_limit
+                .map_or(usize::MAX, |limit| limit.saturating_sub(loading))
+                .min(available)
+                .min(package.unloaded.len());
+            available -= requests;
+            let new = package.unloaded.drain(..requests).map(&mut load);
+            package.assets.extend(new);
+            package.check_loaded(&mut state);
+        }
+    }
+}
+
+pub(crate) fn track_packages<T: ProgressMarker>(
+    mut manifest: ResMut<PackageManifest<T>>,
+    server: Res<AssetServer>,
+    mut progress: ResMut<Progress<T>>,
+) {
+    manifest.update(
+        |path| server.load_untyped(path).untyped(),
+        |id| asset_load(&server, id),
+    );
+    let count = manifest.count();
+    if count != ProgressCount::default() {
+        progress.track_unchecked_source("packages", count);
+    }
+}
+
+/// Returns a system calling the fallback once, if loading is estimated to take too long after `check_after`
+pub(crate) fn fall_back_when_slow<T: ProgressMarker, F>(
+    check_after: Duration,
+    max_eta: Duration,
+    mut fallback: F,
+) -> impl FnMut(Res<Progress<T>>, Option<ResMut<PackageManifest<T>>>)
+where
+    F: FnMut(&Progress<T>, &mut PackageManifest<T>) + Send + Sync + 'static,
+{
+    let mut checked = false;
+    move |progress, manifest| {
+        if checked || progress.loading_time() < check_after {
+            return;
+        }
+        checked = true;
+        let too_slow = progress.eta().is_none_or(|eta| eta > max_eta);
+        if let (true, Some(mut manifest)) = (too_slow, manifest) {
+            fallback(&progress, &mut manifest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::time::Duration;
+
+    use bevy::app::Update;
+    use bevy::asset::{Asset, Handle};
+    use bevy::ecs::system::ResMut;
+    use bevy::reflect::TypePath;
+    use bevy::time::{TimePlugin, TimeUpdateStrategy};
+
+    use crate::assets::AssetLoad;
+    use crate::testing::{self, Loading};
+    use crate::{PackageManifest, Progress, ProgressAppExt, ProgressCount};
+
+    #[derive(Asset, TypePath)]
+    struct Level;
+
+    #[test]
+    fn tracks_assets_and_steps_per_package() {
+        let mut manifest =
+            PackageManifest::<Loading>::parse("[base]\na.ron\nb.ron\n[mod]").unwrap();
+        manifest.add_step("mod", "register items");
+        assert!(!manifest.add_step("missing", "register items"));
+
+        let mut loaded = 0;
+        manifest.update(
+            |_| Handle::<Level>::default().untyped(),
+            |_| {
+                loaded += 1;
+                if loaded == 1 {
+                    AssetLoad::Loaded
+                } else {
+                    AssetLoad::Loading
+                }
+            },
+        );
+        assert_eq!(manifest.package("base"), Some(ProgressCount::new(1, 2)));
+        assert_eq!(manifest.package("mod"), Some(ProgressCount::pending(1)));
+
+        manifest.complete_step("mod", "register items");
+        manifest.update(|_| unreachable!(), |_| AssetLoad::Loaded);
+        assert!(manifest.count().is_complete());
+    }
+
+    #[test]
+    fn limits_concurrent_loads() {
+        let mut manifest =
+            PackageManifest::<Loading>::parse("[base]\na.ron\nb.ron\nc.ron\n[mod]\nd.ron\ne.ron")
+                .unwrap();
+        manifest.set_load_limit(Some(3));
+        manifest.set_package_load_limit("base", Some(2));
+
+        let requested = RefCell::new(Vec::new());
+        let load = |path: String| {
+            requested.borrow_mut().push(path);
+            Handle::<Level>::default().untyped()
+        };
+        manifest.update(load, |_| AssetLoad::Loading);
+        assert_eq!(*requested.borrow(), ["a.ron", "b.ron", "d.ron"]);
+
+        let mut finished = false;
+        manifest.update(load, |_| {
+            if std::mem::replace(&mut finished, true) {
+                AssetLoad::Loading
+            } else {
+                AssetLoad::Loaded
+            }
+        });
+        assert_eq!(*requested.borrow(), ["a.ron", "b.ron", "d.ron", "c.ron"]);
+        assert_eq!(manifest.count(), ProgressCount::new(1, 5));
+
+        assert!(!manifest.set_load_limit(Some(0)));
+        assert!(!manifest.set_package_load_limit("base", Some(0)));
+        assert!(manifest.set_load_limit(None));
+    }
+
+    #[test]
+    fn failed_assets_free_their_load_slot() {
+        let mut manifest = PackageManifest::<Loading>::parse(
+            "[base]
+a.ron
+b.ron
+c.ron",
+        )
+        .unwrap();
+        manifest.set_load_limit(Some(1));
+
+        let requested = RefCell::new(Vec::new());
+        let load = |path: String| {
+            requested.borrow_mut().push(path);
+            Handle::<Level>::default().untyped()
+        };
+        manifest.update(load, |_| AssetLoad::Loading);
+        manifest.update(load, |_| AssetLoad::Failed);
+        assert_eq!(*requested.borrow(), ["a.ron", "b.ron"]);
+        assert_eq!(manifest.failed_assets("base"), Some(2));
+        assert_eq!(manifest.count(), ProgressCount::new(2, 3));
+
+        manifest.update(load, |_| AssetLoad::Loaded);
+        assert_eq!(*requested.borrow(), ["a.ron", "b.ron", "c.ron"]);
+        assert_eq!(manifest.failed_assets("base"), Some(2));
+        assert!(manifest.count().is_complete());
+    }
+
+    #[test]
+    fn replaces_queued_assets() {
+        let mut manifest =
+            PackageManifest::<Loading>::parse("[textures]\nrock.png\nhd/grass.png").unwrap();
+        manifest.retain_queued(|_, path| match path.strip_prefix("hd/") {
+            Some(low) => {
+                *path = format!("low/{low}");
+                true
+            }
+            None => false,
+        });
+
+        let mut requested = Vec::new();
+        manifest.update(
+            |path| {
+                requested.push(path);
+                Handle::<Level>::default().untyped()
+            },
+            |_| AssetLoad::Loading,
+        );
+        assert_eq!(requested, ["low/grass.png"]);
+    }
+
+    #[test]
+    fn rejects_assets_without_package() {
+        let error = PackageManifest::<Loading>::parse("# mods\nlevel.ron").err();
+        assert_eq!(error.map(|error| error.line), Some(2));
+    }
+
+    #[test]
+    fn falls_back_when_loading_is_slow() {
+        let mut app = testing::app();
+        app.add_plugins(TimePlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(1)))
+            .track_packages::<Loading>()
+            .fall_back_when_slow::<Loading, _>(
+                Duration::from_secs(2),
+                Duration::from_secs(10),
+                |progress, manifest| {
+                    assert!(progress
+                        .contributions()
+                        .contains(&("textures", ProgressCount::new(1, 100))));
+                    manifest.retain_queued(|_, _| false);
+                    manifest.add_step("fallback", "low quality");
+                },
+            );
+        let mut manifest = PackageManifest::<Loading>::default();
+        manifest.add_package("fallback");
+        app.insert_resource(manifest).add_systems(
+            Update,
+            |mut progress: ResMut<Progress<Loading>>| {
+                progress.track_source("textures", ProgressCount::new(1, 100))
+            },
+        );
+
+        for _ in 0..4 {
+            app.update();
+        }
+        let manifest = app.world().resource::<PackageManifest<Loading>>();
+        assert_eq!(
+            manifest.package("fallback"),
+            Some(ProgressCount::pending(1))
+        );
+    }
+}
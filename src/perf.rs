@@ -0,0 +1,50 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::ecs::resource::Resource;
+
+/// Time spent in the systems of the tracker `T` during the last frame
+///
+/// Only available with the `perf` feature. The resource is added by the [`ProgressPlugin`](crate::ProgressPlugin).
+#[derive(Resource)]
+pub struct ProgressPerf<T> {
+    asset_polling: Duration,
+    finish_frame: Duration,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for ProgressPerf<T> {
+    fn default() -> Self {
+        ProgressPerf {
+            asset_polling: Duration::ZERO,
+            finish_frame: Duration::ZERO,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> ProgressPerf<T> {
+    /// Time spent polling [`TrackedAssets`](crate::TrackedAssets)
+    pub fn asset_polling(&self) -> Duration {
+        self.asset_polling
+    }
+
+    /// Time spent finishing the frame of the tracker
+    pub fn finish_frame(&self) -> Duration {
+        self.finish_frame
+    }
+
+    /// Total time spent in systems of the tracker
+    pub fn total(&self) -> Duration {
+        self.asset_polling + self.finish_frame
+    }
+
+    #[cfg(feature = "assets")]
+    pub(crate) fn record_asset_polling(&mut self, duration: Duration) {
+        self.asset_polling = duration;
+    }
+
+    pub(crate) fn record_finish_frame(&mut self, duration: Duration) {
+        self.finish_frame = duration;
+    }
+}
@@ -0,0 +1,344 @@
+use std::marker::PhantomData;
+use std::mem;
+
+use bevy::app::{App, Last, Plugin};
+#[cfg(feature = "assets")]
+use bevy::app::{PostUpdate, Update};
+#[cfg(feature = "assets")]
+use bevy::asset::{AssetEvents, AssetServer};
+use bevy::ecs::resource::Resource;
+#[cfg(feature = "assets")]
+use bevy::ecs::schedule::common_conditions::resource_exists;
+use bevy::ecs::schedule::{InternedScheduleLabel, IntoScheduleConfigs, ScheduleLabel, SystemSet};
+use bevy::ecs::system::{Res, ResMut};
+#[cfg(feature = "perf")]
+use bevy::platform::time::Instant;
+
+#[cfg(feature = "assets")]
+use crate::assets::poll_tracked_assets;
+use crate::cleanup::despawn_on_progress;
+use crate::clock::ProgressClockPlugin;
+use crate::owner::release_owned_tasks;
+#[cfg(feature = "perf")]
+use crate::ProgressPerf;
+#[cfg(feature = "assets")]
+use crate::TrackedAssets;
+use crate::{
+    OwnerDespawned, Progress, ProgressClock, ProgressCurve, ProgressMarker, ProgressRegistry,
+    SharedProgress,
+};
+
+/// System sets of the systems added by the [`ProgressPlugin`] of every tracker
+///
+/// Order systems relative to these sets to read progress at a guaranteed point in the frame,
+/// for example to run a minigame on the loading screen.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{ProgressPlugin, ProgressSystems, ProgressView};
+/// # struct Loading;
+/// fn update_minigame(progress: ProgressView<Loading>) {
+///     // This frame's progress
+/// }
+///
+/// App::new()
+///     .add_plugins(ProgressPlugin::<Loading>::new())
+///     .add_systems(Last, update_minigame.after(ProgressSystems::FinishFrame));
+/// ```
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProgressSystems {
+    #[cfg_attr(
+        feature = "assets",
+        doc = "Polling [`TrackedAssets`](crate::TrackedAssets) with the `assets` feature"
+    )]
+    #[cfg_attr(
+        not(feature = "assets"),
+        doc = "Polling `TrackedAssets` with the `assets` feature"
+    )]
+    PollAssets,
+    /// Finishing the frame of the tracker, which evaluates the progress
+    FinishFrame,
+}
+
+/// Plugin managing the [`Progress`] resource with the marker `T`
+///
+/// The plugin finishes the frame of the tracker at the end of every frame in [`Last`],
+/// or in the schedule given to [`ProgressPlugin::finish_frame_in`].
+/// Systems reporting progress should run before that, for example in `Update`.
+/// Progress tracked through [`SharedProgress`] is added to [`Progress`] right before finishing the frame.
+#[cfg_attr(
+    feature = "assets",
+    doc = "With the `assets` feature and an [`AssetServer`](bevy::asset::AssetServer) in the app,
+assets in [`TrackedAssets`](crate::TrackedAssets) are polled in `Update`.
+Load states are updated by the asset server in `PreUpdate`, so polled assets count as loaded in the
+same frame. Asset events are only sent in `PostUpdate` though, so assets resolved through
+[asset events](crate::ProgressAppExt::track_asset_events) count as loaded one frame later.
+See [`ProgressPlugin::with_strict_same_frame`] to avoid that latency."
+)]
+/// The tracker is added to the [`ProgressRegistry`] and the [`ProgressClock`](crate::ProgressClock) is added to the app.
+/// Named tasks in [`OwnedTasks`](crate::OwnedTasks) are resolved when their owner is despawned.
+/// Entities marked with [`DespawnOnProgressComplete`](crate::DespawnOnProgressComplete) or
+/// [`DespawnOnProgressStart`](crate::DespawnOnProgressStart) are despawned after finishing the frame.
+///
+/// With the `web` feature on wasm, every change in progress is mirrored to the hosting web page
+/// as a `bevy-progress` custom event on the window, to drive loading bars outside of the canvas.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::ProgressPlugin;
+/// struct Loading;
+///
+/// App::new().add_plugins(ProgressPlugin::<Loading>::new());
+/// ```
+pub struct ProgressPlugin<T> {
+    curve: Option<ProgressCurve>,
+    completion_debounce: u32,
+    sticky_completion: bool,
+    #[cfg(feature = "assets")]
+    strict_same_frame: bool,
+    finish_schedule: InternedScheduleLabel,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: ProgressMarker> ProgressPlugin<T> {
+    /// Create a new plugin for the tracker with the marker `T`
+    pub fn new() -> Self {
+        ProgressPlugin {
+            curve: None,
+            completion_debounce: 0,
+            sticky_completion: false,
+            #[cfg(feature = "assets")]
+            strict_same_frame: false,
+            finish_schedule: Last.intern(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Derive the progress of the tracker with the given curve
+    ///
+    /// See [`Progress::set_calculator`].
+    pub fn with_curve(mut self, curve: ProgressCurve) -> Self {
+        self.curve = Some(curve);
+        self
+    }
+
+    /// Require the tracker to be done for the given number of consecutive frames before it is ready
+    ///
+    /// See [`Progress::set_completion_debounce`].
+    pub fn with_completion_debounce(mut self, frames: u32) -> Self {
+        self.completion_debounce = frames;
+        self
+    }
+
+    /// Keep the tracker ready once it completed until the session ends
+    ///
+    /// See [`Progress::set_sticky_completion`].
+    pub fn with_sticky_completion(mut self) -> Self {
+        self.sticky_completion = true;
+        self
+    }
+
+    /// Poll tracked assets and resolve asset events in `PostUpdate` after the asset events were sent
+    ///
+    /// All loaded assets are counted in the frame they finished loading in, at the cost of
+    /// polling later in the frame, where it cannot run in parallel with systems in `Update`.
+    #[cfg(feature = "assets")]
+    pub fn with_strict_same_frame(mut self) -> Self {
+        self.strict_same_frame = true;
+        self
+    }
+
+    /// Finish the frames of the tracker in the given schedule instead of [`Last`]
+    ///
+    /// Progress is evaluated once the frame is finished. Finishing earlier, for example in `PostUpdate`,
+    /// lets systems later in the same frame react to the evaluated progress, like hiding the loading screen
+    /// before the unloaded scene is rendered for a frame. Systems reporting progress have to run before
+    /// that schedule; progress reported after it counts towards the next frame.
+    /// Plugins evaluating the finished frames of the tracker, like the [`SessionReportPlugin`](crate::SessionReportPlugin),
+    /// run in the same schedule.
+    pub fn finish_frame_in(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.finish_schedule = schedule.intern();
+        self
+    }
+}
+
+impl<T: ProgressMarker> Default for ProgressPlugin<T> {
+    fn default() -> Self {
+        ProgressPlugin::new()
+    }
+}
+
+impl<T: ProgressMarker> Plugin for ProgressPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.world_mut()
+            .get_resource_or_init::<ProgressRegistry>()
+            .register::<T>();
+        if !app.is_plugin_added::<ProgressClockPlugin>() {
+            app.add_plugins(ProgressClockPlugin);
+        }
+        app.init_resource::<Progress<T>>()
+            .init_resource::<SharedProgress<T>>()
+            .add_event::<OwnerDespawned<T>>()
+            .add_observer(release_owned_tasks::<T>)
+            .add_systems(
+                self.finish_schedule,
+                (
+                    finish_frame::<T>.in_set(ProgressSystems::FinishFrame),
+                    despawn_on_progress::<T>.after(finish_frame::<T>),
+                ),
+            );
+        #[cfg(feature = "assets")]
+        {
+            app.init_resource::<TrackedAssets<T>>();
+            let polling = poll_tracked_assets::<T>
+                .run_if(resource_exists::<AssetServer>)
+                .in_set(ProgressSystems::PollAssets)
+                .before(finish_frame::<T>);
+            if self.strict_same_frame {
+                app.add_systems(PostUpdate, polling.after(AssetEvents));
+            } else {
+                app.add_systems(Update, polling);
+            }
+        }
+        let mut progress = app.world_mut().resource_mut::<Progress<T>>();
+        if let Some(curve) = self.curve {
+            progress.set_calculator(curve);
+        }
+        if self.completion_debounce > 0 {
+            progress.set_completion_debounce(self.completion_debounce);
+        }
+        if self.sticky_completion {
+            progress.set_sticky_completion(true);
+        }
+        #[cfg(feature = "perf")]
+        app.init_resource::<ProgressPerf<T>>();
+        #[cfg(all(feature = "web", target_arch = "wasm32"))]
+        app.add_systems(
+            self.finish_schedule,
+            crate::web::post_progress::<T>.after(ProgressSystems::FinishFrame),
+        );
+        let schedules = TrackerSchedules {
+            finish: self.finish_schedule,
+            #[cfg(feature = "assets")]
+            strict_same_frame: self.strict_same_frame,
+        };
+        let mut setup = app.world_mut().get_resource_or_init::<TrackerSetup<T>>();
+        setup.schedules = Some(schedules);
+        for register in mem::take(&mut setup.pending) {
+            register(app, schedules);
+        }
+    }
+}
+
+/// Schedules the [`ProgressPlugin`] of a tracker runs its systems in
+#[derive(Clone, Copy)]
+pub(crate) struct TrackerSchedules {
+    /// Schedule the frames of the tracker are finished in
+    pub(crate) finish: InternedScheduleLabel,
+    /// Whether tracked assets are polled in `PostUpdate` after the asset events were sent
+    #[cfg(feature = "assets")]
+    pub(crate) strict_same_frame: bool,
+}
+
+type Registration = Box<dyn FnOnce(&mut App, TrackerSchedules) + Send + Sync>;
+
+/// Schedules of the tracker `T` once its [`ProgressPlugin`] is built, and registrations waiting for them
+#[derive(Resource)]
+pub(crate) struct TrackerSetup<T> {
+    schedules: Option<TrackerSchedules>,
+    pending: Vec<Registration>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for TrackerSetup<T> {
+    fn default() -> Self {
+        TrackerSetup {
+            schedules: None,
+            pending: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Add systems depending on the schedules of the tracker `T`
+///
+/// Systems evaluating finished frames go into [`TrackerSchedules::finish`] after [`ProgressSystems::FinishFrame`].
+/// The registration runs right away if the [`ProgressPlugin`] of the tracker was built already
+/// and once it is built otherwise, so plugins and extensions can be added in any order.
+pub(crate) fn with_tracker_schedules<T: ProgressMarker>(
+    app: &mut App,
+    register: impl FnOnce(&mut App, TrackerSchedules) + Send + Sync + 'static,
+) {
+    let mut setup = app.world_mut().get_resource_or_init::<TrackerSetup<T>>();
+    let Some(schedules) = setup.schedules else {
+        setup.pending.push(Box::new(register));
+        return;
+    };
+    register(app, schedules);
+}
+
+/// Run condition that is `true` once the [`ProgressPlugin`] of the tracker `T` is built
+pub(crate) fn tracker_built<T: ProgressMarker>(setup: Option<Res<TrackerSetup<T>>>) -> bool {
+    setup.is_some_and(|setup| setup.schedules.is_some())
+}
+
+pub(crate) fn finish_frame<T: ProgressMarker>(
+    mut progress: ResMut<Progress<T>>,
+    shared: Res<SharedProgress<T>>,
+    clock: Res<ProgressClock>,
+    #[cfg(feature = "perf")] mut perf: ResMut<ProgressPerf<T>>,
+) {
+    #[cfg(feature = "perf")]
+    let start = Instant::now();
+    progress.track_shared(shared.take());
+    progress.finish_frame();
+    progress.add_loading_time(clock.delta());
+    #[cfg(feature = "perf")]
+    perf.record_finish_frame(start.elapsed());
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, Last, PostUpdate, Update};
+    use bevy::ecs::schedule::IntoScheduleConfigs;
+    use bevy::ecs::system::{Res, ResMut};
+
+    use crate::testing::{self, Loading, Ready};
+    use crate::{Progress, ProgressCount, ProgressPlugin, ProgressSystems, ProgressView};
+
+    fn track_done(mut progress: ResMut<Progress<Loading>>) {
+        progress.track(1, 1);
+    }
+
+    #[test]
+    fn orders_systems_after_finishing_frames() {
+        let mut app = testing::app();
+        app.insert_resource(Ready(false))
+            .add_systems(Update, track_done)
+            .add_systems(
+                Last,
+                (|progress: ProgressView<Loading>, mut ready: ResMut<Ready>| {
+                    ready.0 = progress.count() == ProgressCount::done(1)
+                })
+                .after(ProgressSystems::FinishFrame),
+            );
+
+        app.update();
+        assert!(app.world().resource::<Ready>().0);
+    }
+
+    #[test]
+    fn finishing_early_evaluates_in_the_same_frame() {
+        let mut app = App::new();
+        app.add_plugins(ProgressPlugin::<Loading>::new().finish_frame_in(PostUpdate))
+            .insert_resource(Ready(false))
+            .add_systems(Update, track_done)
+            .add_systems(
+                Last,
+                |progress: Res<Progress<Loading>>, mut ready: ResMut<Ready>| {
+                    ready.0 = progress.count() == ProgressCount::done(1)
+                },
+            );
+
+        app.update();
+        assert!(app.world().resource::<Ready>().0);
+    }
+}
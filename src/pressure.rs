@@ -0,0 +1,123 @@
+use std::marker::PhantomData;
+
+use bevy::ecs::event::{Event, EventWriter};
+use bevy::ecs::system::Res;
+
+use crate::{Progress, ProgressCount, ProgressMarker};
+
+/// Event sent when the outstanding work of a source of the tracker `T` rises above or falls back below a threshold
+///
+/// Loaders can back off while the pressure is high, for example to keep decoded images waiting for upload
+/// from running low-end devices out of memory.
+/// See [`ProgressAppExt::send_pressure_events`](crate::ProgressAppExt::send_pressure_events).
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct ProgressPressure<T> {
+    /// The source with the outstanding work
+    pub source: &'static str,
+    /// Number of tasks of the source that are not done
+    pub outstanding: usize,
+    /// `true` if the outstanding work rose above the threshold and `false` if it fell back
+    pub high: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ProgressPressure<T> {
+    pub(crate) fn new(source: &'static str, outstanding: usize, high: bool) -> Self {
+        ProgressPressure {
+            source,
+            outstanding,
+            high,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Returns the new pressure state, if the outstanding work of the count crossed the threshold
+///
+/// Without a count, because the source did not report in the last frame, nothing is outstanding.
+fn pressure_change(
+    high: bool,
+    count: Option<ProgressCount>,
+    threshold: usize,
+) -> Option<(bool, usize)> {
+    let outstanding = count.map_or(0, |count| count.total.saturating_sub(count.done));
+    let now_high = outstanding > threshold;
+    (now_high != high).then_some((now_high, outstanding))
+}
+
+/// Returns a system sending a [`ProgressPressure`] whenever the outstanding work of the source crosses the threshold
+pub(crate) fn send_pressure_events<T: ProgressMarker>(
+    source: &'static str,
+    threshold: usize,
+) -> impl FnMut(Res<Progress<T>>, EventWriter<ProgressPressure<T>>) {
+    let mut high = false;
+    move |progress, mut events| {
+        let count = progress
+            .contributions()
+            .iter()
+            .find(|(name, _)| *name == source)
+            .map(|(_, count)| *count);
+        if let Some((now_high, outstanding)) = pressure_change(high, count, threshold) {
+            high = now_high;
+            events.write(ProgressPressure::new(source, outstanding, high));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, Update};
+    use bevy::ecs::event::Events;
+    use bevy::ecs::system::{Res, ResMut};
+
+    use crate::pressure::pressure_change;
+    use crate::testing::{self, Loading, Ready};
+    use crate::{Progress, ProgressAppExt, ProgressCount, ProgressPressure};
+
+    #[test]
+    fn changes_pressure_when_crossing_the_threshold() {
+        assert_eq!(
+            pressure_change(false, Some(ProgressCount::new(2, 10)), 4),
+            Some((true, 8))
+        );
+        assert_eq!(
+            pressure_change(true, Some(ProgressCount::new(5, 10)), 4),
+            None
+        );
+        assert_eq!(
+            pressure_change(true, Some(ProgressCount::new(6, 10)), 4),
+            Some((false, 4))
+        );
+        assert_eq!(pressure_change(true, None, 4), Some((false, 0)));
+        assert_eq!(pressure_change(false, None, 4), None);
+    }
+
+    #[test]
+    fn sends_pressure_events() {
+        let mut app = testing::app();
+        app.insert_resource(Ready(false))
+            .add_systems(
+                Update,
+                |ready: Res<Ready>, mut progress: ResMut<Progress<Loading>>| {
+                    let done = if ready.0 { 8 } else { 2 };
+                    progress.track_source("uploads", ProgressCount::new(done, 10))
+                },
+            )
+            .send_pressure_events::<Loading>("uploads", 4);
+        let pressure = |app: &App| {
+            app.world()
+                .resource::<Events<ProgressPressure<Loading>>>()
+                .iter_current_update_events()
+                .map(|event| (event.outstanding, event.high))
+                .collect::<Vec<_>>()
+        };
+
+        app.update();
+        assert_eq!(pressure(&app), [(8, true)]);
+        app.update();
+        assert_eq!(pressure(&app), []);
+        app.world_mut().resource_mut::<Ready>().0 = true;
+        app.update();
+        assert_eq!(pressure(&app), [(2, false)]);
+    }
+}
@@ -0,0 +1,1125 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
+use core::marker::PhantomData;
+use core::time::Duration;
+
+#[cfg(feature = "bevy")]
+use bevy::ecs::resource::Resource;
+#[cfg(feature = "bevy")]
+use bevy::log::warn;
+
+use crate::math::monotonic;
+use crate::named::NamedTasks;
+use crate::{GroupCompletion, GroupId, ProgressCalculator, ProgressCount, TaskId};
+
+/// Resource that keeps record of current, previous and persisted progress
+///
+/// The type parameter is a [marker](crate::ProgressMarker) to distinguish multiple trackers in the same app.
+#[cfg_attr(
+    feature = "bevy",
+    doc = "Add a [`ProgressPlugin`](crate::ProgressPlugin) with the same marker to finish every frame automatically."
+)]
+#[cfg_attr(feature = "bevy", derive(Resource))]
+pub struct Progress<T> {
+    current: ProgressCount,
+    previous: ProgressCount,
+    current_soft: ProgressCount,
+    previous_soft: ProgressCount,
+    current_hidden: ProgressCount,
+    previous_hidden: ProgressCount,
+    persisted: Vec<(Cow<'static, str>, PersistScope, ProgressCount)>,
+    named: NamedTasks,
+    current_sources: Sources,
+    previous_sources: Sources,
+    unchecked_sources: Vec<&'static str>,
+    total_regression: TotalRegression,
+    reserved: Vec<(&'static str, usize)>,
+    teardown: Vec<(&'static str, usize)>,
+    calculator: Option<Box<dyn ProgressCalculator>>,
+    displayed: f32,
+    display_smoothing: f32,
+    completion_debounce: u32,
+    ready_frames: u32,
+    sticky_completion: bool,
+    latched: bool,
+    loading_time: Duration,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Counts of the sources in a frame
+///
+/// With the `small_vec` feature, up to 8 sources are stored inline, so even the first frames
+/// of a tracker do not allocate for them.
+#[cfg(feature = "small_vec")]
+type Sources = smallvec::SmallVec<[(&'static str, ProgressCount); 8]>;
+#[cfg(not(feature = "small_vec"))]
+type Sources = Vec<(&'static str, ProgressCount)>;
+
+impl<T> Default for Progress<T> {
+    fn default() -> Self {
+        Progress {
+            current: ProgressCount::default(),
+            previous: ProgressCount::default(),
+            current_soft: ProgressCount::default(),
+            previous_soft: ProgressCount::default(),
+            current_hidden: ProgressCount::default(),
+            previous_hidden: ProgressCount::default(),
+            persisted: Vec::new(),
+            named: NamedTasks::default(),
+            current_sources: Sources::new(),
+            previous_sources: Sources::new(),
+            unchecked_sources: Vec::new(),
+            total_regression: TotalRegression::default(),
+            reserved: Vec::new(),
+            teardown: Vec::new(),
+            calculator: None,
+            displayed: 1.0,
+            display_smoothing: 1.0,
+            completion_debounce: 0,
+            ready_frames: 0,
+            sticky_completion: false,
+            latched: false,
+            loading_time: Duration::ZERO,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Debug for Progress<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Progress")
+            .field("current", &self.current)
+            .field("previous", &self.previous)
+            .field("soft", &self.previous_soft)
+            .field("hidden", &self.previous_hidden)
+            .field("persisted", &self.persisted)
+            .field("named", &self.named.count())
+            .field("sources", &self.previous_sources)
+            .finish()
+    }
+}
+
+/// What to do when a source reports less tasks than in the previous frame
+///
+/// Shrinking totals usually point to a bug and make the progress jump.
+/// See [`Progress::set_total_regression`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TotalRegression {
+    /// Accept the smaller total
+    #[default]
+    Allow,
+    /// Accept the smaller total, but log a warning
+    ///
+    /// Warnings are only logged with the `bevy` feature.
+    Warn,
+    /// Keep the larger total of the previous frame by counting the missing tasks as in progress
+    Clamp,
+}
+
+/// How long persisted tasks are kept
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PersistScope {
+    /// Keep the tasks until the session ends with [`Progress::end_session`]
+    #[default]
+    Session,
+    /// Keep the tasks until they are removed or the tracker is cleared
+    Global,
+}
+
+/// Convenience enum to mark a single task as `in progress` or `done`
+#[derive(PartialEq)]
+pub enum Task {
+    /// Mark a task as done
+    Done,
+    /// Mark a task as in progress
+    InProgress,
+}
+
+impl<T> Progress<T> {
+    /// track the given amount of tasks of wich some can already be completed
+    pub fn track(&mut self, tasks: usize, done: usize) {
+        self.current.total += tasks;
+        self.current.done += done;
+    }
+
+    /// Track the tasks of the given count
+    ///
+    /// ```edition2021
+    /// # use bevy_progress_tracking::{Progress, ProgressCount};
+    /// # struct Loading;
+    /// # let mut progress = Progress::<Loading>::default();
+    /// progress.track_count(ProgressCount::new(1, 3));
+    /// ```
+    /// is the equivalent of `progress.track(3, 1)`.
+    pub fn track_count(&mut self, count: ProgressCount) {
+        self.track(count.total, count.done);
+    }
+
+    /// Track the given amount of soft tasks of which some can already be completed
+    ///
+    /// Soft tasks are best-effort, like preloading optional high resolution textures.
+    /// They are part of [`Progress::progress`] and [`Progress::count`], but do not have to be done
+    /// for the tracker to be [ready](Progress::is_ready).
+    /// ```edition2021
+    /// # use bevy_progress_tracking::Progress;
+    /// # struct Loading;
+    /// # let mut progress = Progress::<Loading>::default();
+    /// progress.track(1, 1);
+    /// progress.track_soft(1, 0);
+    /// progress.finish_frame();
+    /// assert!(progress.is_ready());
+    /// assert_eq!(progress.progress(), 0.5);
+    /// ```
+    pub fn track_soft(&mut self, tasks: usize, done: usize) {
+        self.track(tasks, done);
+        self.current_soft.track(tasks, done);
+    }
+
+    /// Track the soft tasks of the given count
+    ///
+    /// See [`Progress::track_soft`].
+    pub fn track_soft_count(&mut self, count: ProgressCount) {
+        self.track_soft(count.total, count.done);
+    }
+
+    /// Track the given amount of hidden tasks of which some can already be completed
+    ///
+    /// Hidden tasks have to be done for the tracker to be [ready](Progress::is_ready), but are not part of
+    /// [`Progress::progress`] and [`Progress::count`]. They are meant for technical steps like warming up
+    /// physics or navigation meshes that players should not see on the loading bar.
+    pub fn track_hidden(&mut self, tasks: usize, done: usize) {
+        self.current_hidden.track(tasks, done);
+    }
+
+    /// Track the tasks of the given count and attribute them to the given source
+    ///
+    /// The counts of all sources in the last finished frame are available through [`Progress::contributions`].
+    /// All tasks tracked by this crate are attributed to a source.
+    pub fn track_source(&mut self, source: &'static str, count: ProgressCount) {
+        self.track_count(count);
+        match self
+            .current_sources
+            .iter_mut()
+            .find(|(name, _)| *name == source)
+        {
+            Some((_, sum)) => *sum += count,
+            None => self.current_sources.push((source, count)),
+        }
+    }
+
+    /// Track a source whose tasks are managed by this crate, like named tasks
+    ///
+    /// The total of these sources shrinks once tasks are removed, so it is not [checked](Progress::set_total_regression).
+    pub(crate) fn track_unchecked_source(&mut self, source: &'static str, count: ProgressCount) {
+        if !self.unchecked_sources.contains(&source) {
+            self.unchecked_sources.push(source);
+        }
+        self.track_source(source, count);
+    }
+
+    pub(crate) fn track_shared(&mut self, count: ProgressCount) {
+        if count != ProgressCount::default() {
+            self.track_source("shared", count);
+        }
+    }
+
+    /// Returns the counts of all sources in the last finished frame
+    ///
+    /// Only tasks tracked with [`Progress::track_source`] are attributed to a source.
+    /// Persisted and named tasks are listed as the sources `"persisted"` and `"named"`,
+    #[cfg_attr(
+        feature = "bevy",
+        doc = "tasks tracked through [`SharedProgress`](crate::SharedProgress) as `\"shared\"`."
+    )]
+    #[cfg_attr(
+        not(feature = "bevy"),
+        doc = "tasks tracked through `SharedProgress` as `\"shared\"`."
+    )]
+    pub fn contributions(&self) -> &[(&'static str, ProgressCount)] {
+        &self.previous_sources
+    }
+
+    /// Configure the handling of sources reporting less tasks than in the previous frame
+    ///
+    /// Only tasks attributed to a source with [`Progress::track_source`] can be checked. Sources managed by this
+    /// crate, like persisted and named tasks, reserved tasks, [teardown](Progress::track_remaining) or objectives,
+    /// are not checked, because removing their tasks shrinks their totals on purpose.
+    /// Totals are only compared within a session: after a frame in which all tasks were done,
+    /// the next frame may report any total.
+    pub fn set_total_regression(&mut self, total_regression: TotalRegression) {
+        self.total_regression = total_regression;
+    }
+
+    /// Derive [`Progress::progress`] from the counts with the given calculator
+    ///
+    /// Counts and readiness are not affected.
+    pub fn set_calculator(&mut self, calculator: impl ProgressCalculator) {
+        self.calculator = Some(Box::new(calculator));
+    }
+
+    /// Go back to deriving [`Progress::progress`] as the ratio of done tasks
+    pub fn remove_calculator(&mut self) {
+        self.calculator = None;
+    }
+
+    fn check_total_regression(&mut self) {
+        // A new session starts after a frame with all tasks done
+        if self.total_regression == TotalRegression::Allow || self.previous.is_complete() {
+            return;
+        }
+        for (source, count) in self.current_sources.iter_mut() {
+            if self.unchecked_sources.contains(source) {
+                continue;
+            }
+            let Some((_, previous)) = self
+                .previous_sources
+                .iter()
+                .find(|(name, _)| name == source)
+            else {
+                continue;
+            };
+            if count.total >= previous.total {
+                continue;
+            }
+            if self.total_regression == TotalRegression::Warn {
+                #[cfg(feature = "bevy")]
+                warn!(
+                    "The source '{}' reported {} tasks after reporting {} tasks in the previous frame",
+                    source, count.total, previous.total
+                );
+            } else {
+                let missing = previous.total - count.total;
+                count.total += missing;
+                self.current.total += missing;
+            }
+        }
+    }
+
+    /// Reserve the given amount of tasks for a source that did not report yet
+    ///
+    /// Until the source tracks progress for the first time with [`Progress::track_source`],
+    /// the reserved tasks are counted as in progress. This keeps the total stable for chained
+    /// loading steps, where the tasks of the next step are only known once the previous step is done.
+    /// ```edition2021
+    /// # use bevy_progress_tracking::{Progress, ProgressCount};
+    /// # struct Loading;
+    /// # let mut progress = Progress::<Loading>::default();
+    /// progress.reserve("spawn level", 10);
+    /// progress.track_source("load level", ProgressCount::done(5));
+    /// progress.finish_frame();
+    /// assert_eq!(progress.count(), ProgressCount::new(5, 15));
+    ///
+    /// progress.track_source("load level", ProgressCount::done(5));
+    /// progress.track_source("spawn level", ProgressCount::new(2, 8));
+    /// progress.finish_frame();
+    /// assert_eq!(progress.count(), ProgressCount::new(7, 13));
+    /// ```
+    pub fn reserve(&mut self, source: &'static str, tasks: usize) {
+        match self.reserved.iter_mut().find(|(name, _)| *name == source) {
+            Some((_, reserved)) => *reserved = tasks,
+            None => self.reserved.push((source, tasks)),
+        }
+    }
+
+    /// Track work that is done once nothing is left, like despawning a level or freeing assets
+    ///
+    /// This tracks a reverse session for teardown phases: the largest amount of remaining work reported
+    /// by the source is its total and everything below it is done. Once nothing is left, the source
+    /// is tracked as done and the next report starts over. Use a separate marker for teardown, so
+    /// returning to the menu gets its own progress, and only report while tearing down.
+    /// ```edition2021
+    /// # use bevy_progress_tracking::{Progress, ProgressCount};
+    /// struct Unloading;
+    ///
+    /// let mut progress = Progress::<Unloading>::default();
+    /// progress.track_remaining("level entities", 40);
+    /// progress.finish_frame();
+    /// progress.track_remaining("level entities", 10);
+    /// progress.finish_frame();
+    /// assert_eq!(progress.count(), ProgressCount::new(30, 40));
+    /// ```
+    pub fn track_remaining(&mut self, source: &'static str, remaining: usize) {
+        let total = match self.teardown.iter_mut().find(|(name, _)| *name == source) {
+            Some((_, total)) => {
+                *total = (*total).max(remaining);
+                *total
+            }
+            None => {
+                self.teardown.push((source, remaining));
+                remaining
+            }
+        };
+        if remaining == 0 {
+            self.teardown.retain(|(name, _)| *name != source);
+        }
+        self.track_unchecked_source(source, ProgressCount::new(total - remaining, total));
+    }
+
+    fn track_reserved(&mut self) {
+        let sources = &self.current_sources;
+        self.reserved
+            .retain(|(source, _)| !sources.iter().any(|(name, _)| name == source));
+        for index in 0..self.reserved.len() {
+            let (source, tasks) = self.reserved[index];
+            self.track_unchecked_source(source, ProgressCount::pending(tasks));
+        }
+    }
+
+    /// Stop progress tracking for the given frame and clear the current count for the next frame
+    ///
+    /// This function should be called every frame before the progress is evaluated by calling [Progress::progress].
+    #[cfg_attr(
+        feature = "bevy",
+        doc = "The [`ProgressPlugin`](crate::ProgressPlugin) does this at the end of every frame."
+    )]
+    pub fn finish_frame(&mut self) {
+        if self.latched {
+            self.current.clear();
+            self.current_soft.clear();
+            self.current_hidden.clear();
+            self.current_sources.clear();
+            self.unchecked_sources.clear();
+            return;
+        }
+        self.track_reserved();
+        let persisted: ProgressCount = self.persisted.iter().map(|(_, _, count)| count).sum();
+        if persisted != ProgressCount::default() {
+            self.track_unchecked_source("persisted", persisted);
+        }
+        if self.named.count() != ProgressCount::default() {
+            self.track_unchecked_source("named", self.named.count());
+        }
+        self.check_total_regression();
+        self.unchecked_sources.clear();
+        self.previous = self.current;
+        self.current.clear();
+        self.previous_soft = self.current_soft;
+        self.current_soft.clear();
+        self.previous_hidden = self.current_hidden;
+        self.current_hidden.clear();
+        core::mem::swap(&mut self.previous_sources, &mut self.current_sources);
+        self.current_sources.clear();
+        self.update_displayed();
+        self.ready_frames = if self.is_complete_frame() {
+            self.ready_frames.saturating_add(1)
+        } else {
+            0
+        };
+        self.latched = self.sticky_completion && self.is_ready();
+    }
+
+    fn update_displayed(&mut self) {
+        let target = self.progress();
+        self.displayed = if self.previous.is_complete() {
+            1.0
+        } else if self.displayed >= 1.0 {
+            // A new session starts where the tracker is
+            target
+        } else {
+            let smoothed = self.displayed + (target - self.displayed) * self.display_smoothing;
+            monotonic(self.displayed, smoothed)
+        };
+    }
+
+    /// Convenience function to track a single task
+    ///
+    /// ```edition2021
+    /// # use bevy_progress_tracking::{Progress, Task};
+    /// # struct Loading;
+    /// # let mut progress = Progress::<Loading>::default();
+    /// progress.task(Task::Done);
+    /// ```
+    /// is the equivalent of `progress.track(1, 1)`.
+    ///
+    ///
+    /// ```edition2021
+    /// # use bevy_progress_tracking::{Progress, Task};
+    /// # struct Loading;
+    /// # let mut progress = Progress::<Loading>::default();
+    /// progress.task(Task::InProgress);
+    /// ```
+    /// is the equivalent of `progress.track(1, 0)`.
+    pub fn task(&mut self, task: Task) {
+        self.current.task(task);
+    }
+
+    /// Returns the progress as a floating point number between 0 and 1
+    ///
+    /// The values are taken from the last finished frame.
+    /// You probably want to call [Progress::finish_frame] before calling this function.
+    /// See [`Progress::set_calculator`] to derive it differently from the counts.
+    pub fn progress(&self) -> f32 {
+        match &self.calculator {
+            Some(calculator) => calculator.ratio(self.previous).clamp(0., 1.),
+            None => self.previous.ratio(),
+        }
+    }
+
+    /// Returns the progress to display in loading screens as a floating point number between 0 and 1
+    ///
+    /// Unlike [`Progress::progress`], which should be used for gating transitions, the displayed progress
+    /// never goes backwards while tasks are not done, for example when new tasks are discovered,
+    /// and follows [`Progress::progress`] only by the [smoothing](Progress::set_display_smoothing) every frame.
+    /// It is updated in [`Progress::finish_frame`]. Both values converge in the frame all tasks are done:
+    /// the displayed progress is exactly 1 then. When tasks are tracked after that, the displayed progress
+    /// starts over at the current progress.
+    /// ```edition2021
+    /// # use bevy_progress_tracking::Progress;
+    /// # struct Loading;
+    /// let mut progress = Progress::<Loading>::default();
+    /// progress.track(2, 1);
+    /// progress.finish_frame();
+    /// progress.track(4, 1);
+    /// progress.finish_frame();
+    /// assert_eq!(progress.progress(), 0.25);
+    /// assert_eq!(progress.displayed_progress(), 0.5);
+    /// ```
+    pub fn displayed_progress(&self) -> f32 {
+        self.displayed
+    }
+
+    /// Set how far the displayed progress moves towards the progress in every finished frame
+    ///
+    /// A smoothing of `1` moves it all the way, `0.1` moves it by a tenth of the difference.
+    /// The value is clamped between `0.01` and `1`. See [`Progress::displayed_progress`].
+    pub fn set_display_smoothing(&mut self, smoothing: f32) {
+        self.display_smoothing = smoothing.clamp(0.01, 1.0);
+    }
+
+    /// Add the given time to the [loading time](Progress::loading_time)
+    ///
+    /// The time only counts while the last finished frame has tasks that are not done. Otherwise,
+    /// the loading time is reset.
+    #[cfg_attr(
+        feature = "bevy",
+        doc = "The [`ProgressPlugin`](crate::ProgressPlugin) does this with the time of the [`ProgressClock`](crate::ProgressClock) after finishing every frame."
+    )]
+    pub fn add_loading_time(&mut self, time: Duration) {
+        let count = self.count();
+        self.loading_time = if count.total == 0 || count.is_complete() {
+            Duration::ZERO
+        } else {
+            self.loading_time + time
+        };
+    }
+
+    /// Returns for how long the tracker has tasks that are not done
+    pub fn loading_time(&self) -> Duration {
+        self.loading_time
+    }
+
+    /// Returns the estimated time until all tasks are done
+    ///
+    /// The estimate extrapolates the [progress](Progress::progress) over the [loading time](Progress::loading_time).
+    /// It is `None` as long as the tracker has no tasks or no progress was made yet.
+    /// ```edition2021
+    /// # use std::time::Duration;
+    /// # use bevy_progress_tracking::Progress;
+    /// # struct Loading;
+    /// let mut progress = Progress::<Loading>::default();
+    /// progress.track(4, 1);
+    /// progress.finish_frame();
+    /// progress.add_loading_time(Duration::from_secs(2));
+    /// assert_eq!(progress.eta(), Some(Duration::from_secs(6)));
+    /// ```
+    pub fn eta(&self) -> Option<Duration> {
+        let count = self.count();
+        if count.total == 0 {
+            return None;
+        }
+        if count.is_complete() {
+            return Some(Duration::ZERO);
+        }
+        let ratio = self.progress();
+        (ratio > 0. && self.loading_time > Duration::ZERO)
+            .then(|| self.loading_time.mul_f32((1. - ratio) / ratio))
+    }
+
+    /// Returns the done and total tasks of the last finished frame
+    ///
+    /// This includes soft tasks.
+    pub fn count(&self) -> ProgressCount {
+        self.previous
+    }
+
+    /// Returns the done and total soft tasks of the last finished frame
+    pub fn soft_count(&self) -> ProgressCount {
+        self.previous_soft
+    }
+
+    /// Returns the done and total hard tasks of the last finished frame
+    ///
+    /// All tasks that were not tracked as soft tasks are hard tasks.
+    pub fn hard_count(&self) -> ProgressCount {
+        ProgressCount::new(
+            self.previous.done - self.previous_soft.done,
+            self.previous.total - self.previous_soft.total,
+        )
+    }
+
+    /// Returns the done and total hidden tasks of the last finished frame
+    pub fn hidden_count(&self) -> ProgressCount {
+        self.previous_hidden
+    }
+
+    /// Returns `true` if all hard and hidden tasks of the last finished frame are done
+    ///
+    /// Use this to gate transitions and [`Progress::progress`] to display the progress including soft tasks.
+    /// With a [completion debounce](Progress::set_completion_debounce), the tasks have to be done
+    /// in that many finished frames in a row.
+    pub fn is_ready(&self) -> bool {
+        self.is_complete_frame() && self.ready_frames >= self.completion_debounce
+    }
+
+    /// Require all hard and hidden tasks to be done for the given number of consecutive frames
+    /// before the tracker [is ready](Progress::is_ready)
+    ///
+    /// This keeps transitions from firing when tasks discovered late briefly drop the tracker below 100%.
+    /// The default of `0` makes the tracker ready in the first frame all tasks are done.
+    /// ```edition2021
+    /// # use bevy_progress_tracking::Progress;
+    /// # struct Loading;
+    /// let mut progress = Progress::<Loading>::default();
+    /// progress.set_completion_debounce(2);
+    /// progress.track(1, 1);
+    /// progress.finish_frame();
+    /// assert!(!progress.is_ready());
+    /// progress.track(1, 1);
+    /// progress.finish_frame();
+    /// assert!(progress.is_ready());
+    /// ```
+    pub fn set_completion_debounce(&mut self, frames: u32) {
+        self.completion_debounce = frames;
+    }
+
+    /// Latch the tracker once it is [ready](Progress::is_ready) until the session ends
+    ///
+    /// While latched, tasks tracked in later frames are ignored and the counts of the completed frame are kept,
+    /// so the tracker cannot un-complete and confuse state transitions.
+    /// Call [`Progress::end_session`] or [`Progress::clear`] to track tasks in a new session.
+    /// ```edition2021
+    /// # use bevy_progress_tracking::{Progress, ProgressCount};
+    /// # struct Loading;
+    /// let mut progress = Progress::<Loading>::default();
+    /// progress.set_sticky_completion(true);
+    /// progress.track(1, 1);
+    /// progress.finish_frame();
+    /// progress.track(2, 0);
+    /// progress.finish_frame();
+    /// assert_eq!(progress.count(), ProgressCount::done(1));
+    ///
+    /// progress.end_session();
+    /// progress.track(2, 0);
+    /// progress.finish_frame();
+    /// assert_eq!(progress.count(), ProgressCount::pending(2));
+    /// ```
+    pub fn set_sticky_completion(&mut self, sticky: bool) {
+        self.sticky_completion = sticky;
+        self.latched = self.latched && sticky;
+    }
+
+    /// Returns `true` if the tracker is latched by [sticky completion](Progress::set_sticky_completion)
+    pub fn is_latched(&self) -> bool {
+        self.latched
+    }
+
+    fn is_complete_frame(&self) -> bool {
+        self.hard_count().is_complete() && self.previous_hidden.is_complete()
+    }
+
+    /// Persist the given amount of tasks and mark them all as done
+    ///
+    /// Running the following once:
+    /// ```edition2021
+    /// # use bevy_progress_tracking::Progress;
+    /// # struct Loading;
+    /// # let mut progress = Progress::<Loading>::default();
+    /// progress.persist_done_tasks(42);
+    /// ```
+    /// is the equivalent of calling `progress.track(42, 42)` in *every* frame.
+    ///
+    /// The tasks are added to the anonymous persisted entry of the session, see [`Progress::persist`].
+    pub fn persist_done_tasks(&mut self, done: usize) {
+        self.persist_in(PersistScope::Session, ProgressCount::done(done));
+    }
+
+    /// Persist the given amount of done tasks
+    ///
+    /// Running the following once:
+    /// ```edition2021
+    /// # use bevy_progress_tracking::Progress;
+    /// # struct Loading;
+    /// # let mut progress = Progress::<Loading>::default();
+    /// # progress.persist_tasks(42);
+    /// progress.persist_done(42);
+    /// ```
+    /// is the equivalent of calling `progress.track(0, 42)` in every later *every* frame.
+    pub fn persist_done(&mut self, done: usize) {
+        self.persist_in(PersistScope::Session, ProgressCount::new(done, 0));
+    }
+
+    /// Persist the given amount of tasks
+    ///
+    /// Running the following once:
+    /// ```edition2021
+    /// # use bevy_progress_tracking::Progress;
+    /// # struct Loading;
+    /// # let mut progress = Progress::<Loading>::default();
+    /// progress.persist_tasks(42);
+    /// ```
+    /// is the equivalent of calling `progress.track(42, 0)` in every later *every* frame.
+    pub fn persist_tasks(&mut self, tasks: usize) {
+        self.persist_in(PersistScope::Session, ProgressCount::pending(tasks));
+    }
+
+    /// Add a named task that counts as in progress in every frame until it is completed
+    ///
+    /// Named tasks are kept until they are removed, like persisted tasks.
+    /// ```edition2021
+    /// # use bevy_progress_tracking::Progress;
+    /// # struct Loading;
+    /// # let mut progress = Progress::<Loading>::default();
+    /// let task = progress.add_named_task("generate terrain");
+    /// // ...
+    /// progress.complete_named_task(task);
+    /// ```
+    pub fn add_named_task(&mut self, label: impl Into<Cow<'static, str>>) -> TaskId {
+        self.named.add(label.into())
+    }
+
+    /// Mark the named task as done
+    ///
+    /// Returns `false` if the task does not exist.
+    pub fn complete_named_task(&mut self, task: TaskId) -> bool {
+        self.named.complete(task)
+    }
+
+    /// Remove the named task
+    ///
+    /// Returns `false` if the task does not exist.
+    pub fn remove_named_task(&mut self, task: TaskId) -> bool {
+        self.named.remove(task)
+    }
+
+    /// Returns the label of the named task and whether it is done
+    pub fn named_task(&self, task: TaskId) -> Option<(&str, bool)> {
+        self.named.get(task)
+    }
+
+    /// Iterate over all named tasks with their label and whether they are done
+    pub fn named_tasks(&self) -> impl Iterator<Item = (TaskId, &str, bool)> {
+        self.named.iter()
+    }
+
+    /// Group named tasks to give them a shared completion
+    ///
+    /// Groups do not add tasks of their own. With [`GroupCompletion::Any`], the remaining tasks
+    /// are counted as done once any task of the group is done.
+    /// ```edition2021
+    /// # use bevy_progress_tracking::{GroupCompletion, Progress, ProgressCount};
+    /// # struct Loading;
+    /// # let mut progress = Progress::<Loading>::default();
+    /// let cached = progress.add_named_task("load cached world");
+    /// let generated = progress.add_named_task("generate world");
+    /// let world = progress.add_task_group(GroupCompletion::Any, [cached, generated]);
+    ///
+    /// progress.complete_named_task(cached);
+    /// assert_eq!(progress.task_group(world), Some(ProgressCount::done(2)));
+    /// ```
+    pub fn add_task_group(
+        &mut self,
+        completion: GroupCompletion,
+        tasks: impl IntoIterator<Item = TaskId>,
+    ) -> GroupId {
+        self.named
+            .add_group(completion, tasks.into_iter().collect())
+    }
+
+    /// Remove the group without removing its tasks
+    ///
+    /// Returns `false` if the group does not exist.
+    pub fn remove_task_group(&mut self, group: GroupId) -> bool {
+        self.named.remove_group(group)
+    }
+
+    /// Returns the done and total tasks of the group
+    ///
+    /// Removed tasks are no longer part of their groups. A group without tasks is complete.
+    pub fn task_group(&self, group: GroupId) -> Option<ProgressCount> {
+        self.named.group(group)
+    }
+
+    /// Persist the tasks of the given count in the given scope
+    ///
+    /// The tasks are added to the anonymous persisted entry of the scope.
+    /// The existing `persist_*` methods persist in [`PersistScope::Session`].
+    /// Tasks persisted in [`PersistScope::Global`] survive [`Progress::end_session`], so they
+    /// should be used sparingly; they are part of the total in every later session.
+    pub fn persist_in(&mut self, scope: PersistScope, count: ProgressCount) {
+        self.persisted_mut("", scope).track(count.total, count.done);
+    }
+
+    /// Persist a labeled entry of the given amount of tasks of which some are done
+    ///
+    /// Persisting a label again replaces the tasks of the entry.
+    /// Labeled entries can be queried, updated and removed individually.
+    /// ```edition2021
+    /// # use bevy_progress_tracking::{PersistScope, Progress, ProgressCount};
+    /// # struct Loading;
+    /// # let mut progress = Progress::<Loading>::default();
+    /// progress.persist("tutorial_done", 1, 0);
+    /// // ...
+    /// progress.persist("tutorial_done", 1, 1);
+    /// assert_eq!(
+    ///     progress.persisted_entry("tutorial_done", PersistScope::Session),
+    ///     Some(ProgressCount::done(1))
+    /// );
+    /// ```
+    pub fn persist(&mut self, label: impl Into<Cow<'static, str>>, tasks: usize, done: usize) {
+        self.persist_entry(
+            label,
+            PersistScope::Session,
+            ProgressCount::new(done, tasks),
+        );
+    }
+
+    /// Persist a labeled entry with the tasks of the given count in the given scope
+    ///
+    /// Like [`Progress::persist`], this replaces the tasks of an existing entry with the same label and scope.
+    pub fn persist_entry(
+        &mut self,
+        label: impl Into<Cow<'static, str>>,
+        scope: PersistScope,
+        count: ProgressCount,
+    ) {
+        let label = label.into();
+        debug_assert!(
+            count.total >= count.done,
+            "The persisted entry '{}' has more done tasks than there are tasks",
+            label
+        );
+        match self.find_persisted(&label, scope) {
+            Some(index) => self.persisted[index].2 = count,
+            None => self.persisted.push((label, scope, count)),
+        }
+    }
+
+    /// Returns the tasks of the persisted entry with the given label and scope
+    ///
+    /// Tasks persisted without a label are in the entry with the empty label `""`.
+    pub fn persisted_entry(&self, label: &str, scope: PersistScope) -> Option<ProgressCount> {
+        self.find_persisted(label, scope)
+            .map(|index| self.persisted[index].2)
+    }
+
+    /// Remove the persisted entry with the given label and scope and return its tasks
+    pub fn remove_persisted_entry(
+        &mut self,
+        label: &str,
+        scope: PersistScope,
+    ) -> Option<ProgressCount> {
+        self.find_persisted(label, scope)
+            .map(|index| self.persisted.remove(index).2)
+    }
+
+    /// Returns the persisted tasks of all entries in the given scope
+    pub fn persisted(&self, scope: PersistScope) -> ProgressCount {
+        self.persisted
+            .iter()
+            .filter(|(_, entry_scope, _)| *entry_scope == scope)
+            .map(|(_, _, count)| count)
+            .sum()
+    }
+
+    /// Iterate over all persisted entries with their label, scope and tasks
+    pub fn persisted_entries(
+        &self,
+    ) -> impl Iterator<Item = (&str, PersistScope, ProgressCount)> + '_ {
+        self.persisted
+            .iter()
+            .map(|(label, scope, count)| (label.as_ref(), *scope, *count))
+    }
+
+    /// Remove all persisted entries of the given scope and return their tasks
+    pub fn remove_persisted(&mut self, scope: PersistScope) -> ProgressCount {
+        let removed = self.persisted(scope);
+        self.persisted
+            .retain(|(_, entry_scope, _)| *entry_scope != scope);
+        removed
+    }
+
+    fn find_persisted(&self, label: &str, scope: PersistScope) -> Option<usize> {
+        self.persisted
+            .iter()
+            .position(|(entry_label, entry_scope, _)| entry_label == label && *entry_scope == scope)
+    }
+
+    fn persisted_mut(&mut self, label: &'static str, scope: PersistScope) -> &mut ProgressCount {
+        let index = self.find_persisted(label, scope).unwrap_or_else(|| {
+            self.persisted
+                .push((label.into(), scope, ProgressCount::default()));
+            self.persisted.len() - 1
+        });
+        &mut self.persisted[index].2
+    }
+
+    /// End the current loading session
+    ///
+    /// Like [`Progress::clear`], but tasks persisted in [`PersistScope::Global`] are kept.
+    pub fn end_session(&mut self) {
+        let persisted = core::mem::take(&mut self.persisted);
+        self.clear();
+        self.persisted = persisted;
+        self.remove_persisted(PersistScope::Session);
+    }
+
+    /// Clear the progress resource
+    ///
+    /// This effectively resets all records
+    pub fn clear(&mut self) {
+        self.current.clear();
+        self.previous.clear();
+        self.current_soft.clear();
+        self.previous_soft.clear();
+        self.current_hidden.clear();
+        self.previous_hidden.clear();
+        self.persisted.clear();
+        self.named.clear();
+        self.current_sources.clear();
+        self.previous_sources.clear();
+        self.unchecked_sources.clear();
+        self.reserved.clear();
+        self.teardown.clear();
+        self.displayed = 1.0;
+        self.ready_frames = 0;
+        self.latched = false;
+        self.loading_time = Duration::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use crate::{PersistScope, Progress, ProgressCount, TotalRegression};
+
+    struct Loading;
+
+    #[test]
+    fn resets_loading_time_once_complete() {
+        let mut progress = Progress::<Loading>::default();
+        progress.add_loading_time(Duration::from_secs(1));
+        assert_eq!(progress.loading_time(), Duration::ZERO);
+
+        progress.track(2, 0);
+        progress.finish_frame();
+        progress.add_loading_time(Duration::from_secs(1));
+        assert_eq!(progress.eta(), None);
+
+        progress.track(2, 2);
+        progress.finish_frame();
+        progress.add_loading_time(Duration::from_secs(1));
+        assert_eq!(progress.loading_time(), Duration::ZERO);
+        assert_eq!(progress.eta(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn smooths_displayed_progress() {
+        let mut progress = Progress::<Loading>::default();
+        progress.set_display_smoothing(0.5);
+        progress.track(4, 2);
+        progress.finish_frame();
+        assert_eq!(progress.displayed_progress(), 0.5);
+
+        progress.track(4, 4);
+        progress.persist_tasks(1);
+        progress.finish_frame();
+        assert_eq!(progress.progress(), 0.8);
+        assert_eq!(progress.displayed_progress(), 0.65);
+
+        progress.track(4, 0);
+        progress.finish_frame();
+        assert_eq!(progress.displayed_progress(), 0.65);
+
+        progress.clear();
+        progress.track(2, 2);
+        progress.finish_frame();
+        assert_eq!(progress.displayed_progress(), 1.0);
+    }
+
+    #[test]
+    fn debounces_completion() {
+        let mut progress = Progress::<Loading>::default();
+        progress.set_completion_debounce(3);
+        for done in [1, 1, 0, 1, 1] {
+            progress.track(1, done);
+            progress.finish_frame();
+            assert!(!progress.is_ready());
+        }
+        progress.track(1, 1);
+        progress.track_soft(1, 0);
+        progress.finish_frame();
+        assert!(progress.is_ready());
+    }
+
+    #[test]
+    fn latches_sticky_completion() {
+        let mut progress = Progress::<Loading>::default();
+        progress.set_sticky_completion(true);
+        progress.set_completion_debounce(2);
+        progress.track(1, 1);
+        progress.finish_frame();
+        assert!(!progress.is_latched());
+        progress.track(1, 1);
+        progress.finish_frame();
+        assert!(progress.is_latched());
+
+        progress.track(3, 0);
+        progress.add_named_task("late");
+        progress.finish_frame();
+        assert!(progress.is_ready());
+        assert_eq!(progress.count(), ProgressCount::done(1));
+
+        progress.set_sticky_completion(false);
+        progress.track(3, 0);
+        progress.finish_frame();
+        assert_eq!(progress.count(), ProgressCount::pending(4));
+    }
+
+    #[test]
+    fn tracks_remaining_teardown_work() {
+        let mut progress = Progress::<Loading>::default();
+        for remaining in [4, 6, 3, 0] {
+            progress.track_remaining("despawn", remaining);
+            progress.finish_frame();
+        }
+        assert_eq!(progress.count(), ProgressCount::done(6));
+
+        progress.track_remaining("despawn", 2);
+        progress.finish_frame();
+        assert_eq!(progress.count(), ProgressCount::pending(2));
+    }
+
+    #[test]
+    fn correctly_tracks_persistent_tasks() {
+        let mut progress = Progress::<Loading>::default();
+        progress.persist_tasks(3);
+        progress.persist_done(1);
+        progress.persist_done_tasks(3);
+        assert_eq!(progress.current, ProgressCount::default());
+        assert_eq!(progress.previous, ProgressCount::default());
+
+        progress.finish_frame();
+        assert_eq!(progress.progress(), 4. / 6.);
+        assert_eq!(progress.current, ProgressCount::default());
+        assert_eq!(progress.previous, ProgressCount { total: 6, done: 4 });
+
+        progress.finish_frame();
+        assert_eq!(progress.progress(), 4. / 6.);
+    }
+
+    #[test]
+    fn attributes_counts_to_sources() {
+        let mut progress = Progress::<Loading>::default();
+        progress.persist_tasks(1);
+        progress.track_source("terrain", ProgressCount::done(2));
+        progress.track_source("trees", ProgressCount::pending(1));
+        progress.track_source("terrain", ProgressCount::pending(1));
+        progress.track(5, 5);
+
+        progress.finish_frame();
+        assert_eq!(progress.count(), ProgressCount::new(7, 10));
+        assert_eq!(
+            progress.contributions(),
+            &[
+                ("terrain", ProgressCount::new(2, 3)),
+                ("trees", ProgressCount::pending(1)),
+                ("persisted", ProgressCount::pending(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn clamps_shrinking_totals() {
+        let mut progress = Progress::<Loading>::default();
+        progress.set_total_regression(TotalRegression::Clamp);
+        progress.track_source("chunks", ProgressCount::new(1, 4));
+        progress.finish_frame();
+
+        progress.track_source("chunks", ProgressCount::new(2, 3));
+        progress.finish_frame();
+        assert_eq!(progress.count(), ProgressCount::new(2, 4));
+
+        progress.track_source("chunks", ProgressCount::new(4, 4));
+        progress.finish_frame();
+        assert_eq!(progress.count(), ProgressCount::done(4));
+
+        // The next session starts with a smaller total
+        progress.track_source("chunks", ProgressCount::new(0, 2));
+        progress.finish_frame();
+        assert_eq!(progress.count(), ProgressCount::pending(2));
+    }
+
+    #[test]
+    fn does_not_clamp_removed_named_tasks() {
+        let mut progress = Progress::<Loading>::default();
+        progress.set_total_regression(TotalRegression::Clamp);
+        let level = progress.add_named_task("level");
+        let music = progress.add_named_task("music");
+        progress.finish_frame();
+        assert_eq!(progress.count(), ProgressCount::pending(2));
+
+        progress.complete_named_task(level);
+        progress.remove_named_task(music);
+        progress.finish_frame();
+        assert_eq!(progress.count(), ProgressCount::done(1));
+        assert!(progress.is_ready());
+    }
+
+    #[test]
+    fn ending_session_keeps_global_tasks() {
+        let mut progress = Progress::<Loading>::default();
+        progress.persist_done_tasks(2);
+        progress.persist_in(PersistScope::Global, ProgressCount::done(1));
+        progress.finish_frame();
+        assert_eq!(progress.count(), ProgressCount::done(3));
+
+        progress.end_session();
+        progress.finish_frame();
+        assert_eq!(progress.count(), ProgressCount::done(1));
+        assert_eq!(
+            progress.persisted_entries().collect::<Vec<_>>(),
+            vec![("", PersistScope::Global, ProgressCount::done(1))]
+        );
+    }
+
+    #[test]
+    fn updates_labeled_persisted_entries() {
+        let mut progress = Progress::<Loading>::default();
+        progress.persist_tasks(1);
+        progress.persist("tutorial_done", 1, 0);
+        progress.persist_entry("intro_seen", PersistScope::Global, ProgressCount::done(1));
+        progress.finish_frame();
+        assert_eq!(progress.count(), ProgressCount::new(1, 3));
+
+        progress.persist("tutorial_done", 1, 1);
+        assert_eq!(
+            progress.remove_persisted_entry("", PersistScope::Session),
+            Some(ProgressCount::pending(1))
+        );
+        assert_eq!(
+            progress.persisted_entry("intro_seen", PersistScope::Session),
+            None
+        );
+        progress.finish_frame();
+        assert_eq!(progress.count(), ProgressCount::done(2));
+        assert_eq!(
+            progress.persisted(PersistScope::Session),
+            ProgressCount::done(1)
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use std::any::type_name;
+
+use bevy::ecs::world::World;
+
+use crate::{Progress, ProgressCount, ProgressMarker};
+
+/// Trait for plugins to report their progress to a tracker of the app
+///
+/// Library authors can implement this for a type of their plugin,
+/// while the app decides which tracker the progress is reported to
+/// with [`ProgressAppExt::register_progress_provider`](crate::ProgressAppExt::register_progress_provider).
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{ProgressAppExt, ProgressCount, ProgressPlugin, ProgressProvider};
+/// #[derive(Resource)]
+/// struct GeneratedChunks(usize);
+///
+/// struct WorldGenerationProgress {
+///     chunks: usize,
+/// }
+///
+/// impl ProgressProvider for WorldGenerationProgress {
+///     fn progress(&mut self, world: &World) -> ProgressCount {
+///         let generated = world.get_resource::<GeneratedChunks>().map_or(0, |chunks| chunks.0);
+///         ProgressCount::new(generated, self.chunks)
+///     }
+/// }
+///
+/// # struct Loading;
+/// App::new()
+///     .add_plugins(ProgressPlugin::<Loading>::new())
+///     .register_progress_provider::<Loading, _>(WorldGenerationProgress { chunks: 64 });
+/// ```
+pub trait ProgressProvider: Send + Sync + 'static {
+    /// Report the current progress
+    ///
+    /// This is called once per frame.
+    fn progress(&mut self, world: &World) -> ProgressCount;
+}
+
+/// Returns a system tracking the progress of the provider as part of the tracker `T`
+pub(crate) fn track_provider<T: ProgressMarker, P: ProgressProvider>(
+    mut provider: P,
+) -> impl FnMut(&mut World) {
+    move |world| {
+        let count = provider.progress(world);
+        world
+            .resource_mut::<Progress<T>>()
+            .track_source(type_name::<P>(), count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::world::World;
+
+    use crate::testing::{self, Loading};
+    use crate::{ProgressAppExt, ProgressCount, ProgressProvider};
+
+    struct Steps(usize);
+
+    impl ProgressProvider for Steps {
+        fn progress(&mut self, _world: &World) -> ProgressCount {
+            self.0 += 1;
+            ProgressCount::new(self.0, 3)
+        }
+    }
+
+    #[test]
+    fn reports_provider_progress() {
+        let mut app = testing::app();
+        app.register_progress_provider::<Loading, _>(Steps(0));
+
+        app.update();
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::new(2, 3));
+    }
+}
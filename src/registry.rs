@@ -0,0 +1,169 @@
+use std::any::{type_name, TypeId};
+
+use bevy::ecs::resource::Resource;
+use bevy::ecs::world::World;
+
+use crate::{Progress, ProgressCount, ProgressMarker};
+
+/// Resource listing the trackers of all added [`ProgressPlugin`](crate::ProgressPlugin)s
+///
+/// Generic tooling, like debug overlays, can use it to show all trackers without knowing their marker types.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{ProgressPlugin, ProgressRegistry};
+/// # struct Loading;
+/// fn print_trackers(world: &World) {
+///     for tracker in world.resource::<ProgressRegistry>().iter() {
+///         println!("{}: {:?}", tracker.name(), tracker.count(world));
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins(ProgressPlugin::<Loading>::new())
+///     .add_systems(Update, print_trackers);
+/// ```
+#[derive(Resource, Default)]
+pub struct ProgressRegistry {
+    trackers: Vec<RegisteredTracker>,
+}
+
+/// Tracker in the [`ProgressRegistry`]
+#[derive(Clone, Copy, Debug)]
+pub struct RegisteredTracker {
+    name: &'static str,
+    type_id: TypeId,
+    count: fn(&World) -> Option<ProgressCount>,
+    progress: fn(&World) -> Option<f32>,
+    pending_tasks: fn(&World) -> Option<Vec<String>>,
+    clear: fn(&mut World),
+}
+
+impl RegisteredTracker {
+    /// Returns the type name of the marker
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the type id of the marker
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Returns the done and total tasks of the last finished frame
+    ///
+    /// Returns `None` if the [`Progress`] resource of the tracker was removed.
+    pub fn count(&self, world: &World) -> Option<ProgressCount> {
+        (self.count)(world)
+    }
+
+    /// Returns the [progress](Progress::progress) of the last finished frame as a floating point number between 0 and 1
+    ///
+    /// The progress is derived with the calculator of the tracker, like a [`ProgressCurve`](crate::ProgressCurve).
+    pub fn ratio(&self, world: &World) -> Option<f32> {
+        (self.progress)(world)
+    }
+
+    /// Returns the labels of the [named tasks](Progress::add_named_task) that are not done
+    ///
+    /// Returns `None` if the [`Progress`] resource of the tracker was removed.
+    pub fn pending_tasks(&self, world: &World) -> Option<Vec<String>> {
+        (self.pending_tasks)(world)
+    }
+
+    /// [Clear](Progress::clear) the [`Progress`] resource of the tracker
+    pub fn clear(&self, world: &mut World) {
+        (self.clear)(world)
+    }
+}
+
+impl ProgressRegistry {
+    /// Iterate over all registered trackers in the order their plugins were added
+    pub fn iter(&self) -> impl Iterator<Item = &RegisteredTracker> {
+        self.trackers.iter()
+    }
+
+    /// Returns the tracker with the marker `T`
+    pub fn get<T: 'static>(&self) -> Option<&RegisteredTracker> {
+        self.get_by_id(TypeId::of::<T>())
+    }
+
+    /// Returns the tracker with the marker of the given type id
+    pub fn get_by_id(&self, type_id: TypeId) -> Option<&RegisteredTracker> {
+        self.trackers
+            .iter()
+            .find(|tracker| tracker.type_id == type_id)
+    }
+
+    pub(crate) fn register<T: ProgressMarker>(&mut self) {
+        if self.get::<T>().is_some() {
+            return;
+        }
+        self.trackers.push(RegisteredTracker {
+            name: type_name::<T>(),
+            type_id: TypeId::of::<T>(),
+            count: |world| world.get_resource::<Progress<T>>().map(Progress::count),
+            progress: |world| world.get_resource::<Progress<T>>().map(Progress::progress),
+            pending_tasks: |world| {
+                let progress = world.get_resource::<Progress<T>>()?;
+                let pending = progress.named_tasks().filter(|(_, _, done)| !done);
+                Some(pending.map(|(_, label, _)| label.to_owned()).collect())
+            },
+            clear: |world| {
+                if let Some(mut progress) = world.get_resource_mut::<Progress<T>>() {
+                    progress.clear();
+                }
+            },
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, Update};
+    use bevy::ecs::system::ResMut;
+
+    use crate::{Progress, ProgressCount, ProgressCurve, ProgressPlugin, ProgressRegistry};
+
+    struct Loading;
+    struct Saving;
+
+    #[test]
+    fn enumerates_trackers() {
+        let mut app = App::new();
+        app.add_plugins((
+            ProgressPlugin::<Loading>::new(),
+            ProgressPlugin::<Saving>::new(),
+        ))
+        .add_systems(Update, |mut progress: ResMut<Progress<Loading>>| {
+            progress.track(4, 1)
+        });
+        app.update();
+
+        let registry = app.world().resource::<ProgressRegistry>();
+        let names: Vec<_> = registry.iter().map(|tracker| tracker.name()).collect();
+        assert_eq!(
+            names,
+            [
+                "bevy_progress_tracking::registry::tests::Loading",
+                "bevy_progress_tracking::registry::tests::Saving"
+            ]
+        );
+        let loading = registry.get::<Loading>().unwrap();
+        assert_eq!(loading.count(app.world()), Some(ProgressCount::new(1, 4)));
+        assert_eq!(loading.ratio(app.world()), Some(0.25));
+    }
+
+    #[test]
+    fn ratio_follows_the_curve() {
+        let mut app = App::new();
+        app.add_plugins(ProgressPlugin::<Loading>::new().with_curve(ProgressCurve::Stepped(2)))
+            .add_systems(Update, |mut progress: ResMut<Progress<Loading>>| {
+                progress.track(4, 1)
+            });
+        app.update();
+
+        let registry = app.world().resource::<ProgressRegistry>();
+        let loading = registry.get::<Loading>().unwrap();
+        assert_eq!(loading.ratio(app.world()), Some(0.));
+    }
+}
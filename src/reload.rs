@@ -0,0 +1,135 @@
+use std::marker::PhantomData;
+
+use bevy::asset::{Asset, AssetServer, Assets, RecursiveDependencyLoadState, UntypedAssetId};
+use bevy::ecs::event::{Event, EventWriter};
+use bevy::ecs::system::{Local, Res, ResMut};
+
+use crate::{Progress, ProgressCount, ProgressMarker};
+
+/// Event sent when a loaded asset starts or finishes reloading
+///
+/// See [`ProgressAppExt::track_reloads`](crate::ProgressAppExt::track_reloads).
+#[derive(Event, Debug)]
+pub struct AssetReload<T> {
+    /// The reloading asset
+    pub id: UntypedAssetId,
+    /// `false` when the reload started and `true` when the asset and its dependencies are loaded again
+    pub finished: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+#[derive(Default)]
+pub(crate) struct Reloads {
+    reloading: Vec<UntypedAssetId>,
+    finished: usize,
+}
+
+impl Reloads {
+    /// Update the reloading assets and return the count of the open reload session
+    ///
+    /// The session closes after the frame all reloads are finished in.
+    fn update(
+        &mut self,
+        loaded: impl IntoIterator<Item = UntypedAssetId>,
+        mut is_reloading: impl FnMut(UntypedAssetId) -> bool,
+        mut reload: impl FnMut(UntypedAssetId, bool),
+    ) -> Option<ProgressCount> {
+        let before = self.reloading.len();
+        self.reloading.retain(|id| {
+            let reloading = is_reloading(*id);
+            if !reloading {
+                reload(*id, true);
+            }
+            reloading
+        });
+        self.finished += before - self.reloading.len();
+        for id in loaded {
+            if !self.reloading.contains(&id) && is_reloading(id) {
+                self.reloading.push(id);
+                reload(id, false);
+            }
+        }
+        let count = ProgressCount::new(self.finished, self.finished + self.reloading.len());
+        if self.reloading.is_empty() {
+            self.finished = 0;
+        }
+        (count.total > 0).then_some(count)
+    }
+}
+
+pub(crate) fn track_reloads<T: ProgressMarker, A: Asset>(
+    assets: Res<Assets<A>>,
+    server: Res<AssetServer>,
+    mut reloads: Local<Reloads>,
+    mut progress: ResMut<Progress<T>>,
+    mut events: EventWriter<AssetReload<T>>,
+) {
+    let count = reloads.update(
+        assets.ids().map(|id| id.untyped()),
+        |id| {
+            matches!(
+                server.get_recursive_dependency_load_state(id),
+                Some(RecursiveDependencyLoadState::Loading)
+            )
+        },
+        |id, finished| {
+            events.write(AssetReload {
+                id,
+                finished,
+                _marker: PhantomData,
+            });
+        },
+    );
+    if let Some(count) = count {
+        progress.track_unchecked_source("reloads", count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::asset::{Asset, AssetId, AssetIndex, UntypedAssetId};
+    use bevy::reflect::TypePath;
+
+    use crate::reload::Reloads;
+    use crate::ProgressCount;
+
+    #[derive(Asset, TypePath)]
+    struct Level;
+
+    #[test]
+    fn opens_a_session_while_assets_reload() {
+        let ids: Vec<UntypedAssetId> = (0..3)
+            .map(|index| AssetId::<Level>::from(AssetIndex::from_bits(index)).untyped())
+            .collect();
+        let mut reloads = Reloads::default();
+        let mut events = Vec::new();
+        let mut update = |reloads: &mut Reloads, reloading: &[UntypedAssetId]| {
+            reloads.update(
+                ids.iter().copied(),
+                |id| reloading.contains(&id),
+                |id, finished| events.push((id, finished)),
+            )
+        };
+
+        assert_eq!(update(&mut reloads, &[]), None);
+        assert_eq!(
+            update(&mut reloads, &[ids[0], ids[2]]),
+            Some(ProgressCount::pending(2))
+        );
+        assert_eq!(
+            update(&mut reloads, &[ids[2]]),
+            Some(ProgressCount::new(1, 2))
+        );
+        assert_eq!(update(&mut reloads, &[]), Some(ProgressCount::done(2)));
+        assert_eq!(update(&mut reloads, &[]), None);
+        assert_eq!(
+            events,
+            [
+                (ids[0], false),
+                (ids[2], false),
+                (ids[0], true),
+                (ids[2], true)
+            ]
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use bevy::app::{App, Plugin};
+use bevy::ecs::system::In;
+use bevy::ecs::world::World;
+use bevy::log::warn;
+use bevy::remote::{BrpResult, RemoteMethodSystemId, RemoteMethods};
+use serde_json::{json, Value};
+
+use crate::ProgressRegistry;
+
+/// Name of the Bevy Remote Protocol method added by the [`ProgressRemotePlugin`]
+pub const BRP_PROGRESS_METHOD: &str = "progress/trackers";
+
+/// Plugin adding the [`BRP_PROGRESS_METHOD`] to the Bevy Remote Protocol
+///
+/// The method takes no parameters and responds with every tracker in the [`ProgressRegistry`],
+/// including the labels of its pending [named tasks](crate::Progress::add_named_task).
+/// Editors and other tools can use it to show the loading state of a running game.
+/// ```json
+/// [{ "name": "game::Loading", "done": 3, "total": 4, "progress": 0.75, "pending_tasks": ["spawn player"] }]
+/// ```
+/// Requires the `RemotePlugin` of Bevy, which can be added before or after this plugin.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy::remote::RemotePlugin;
+/// # use bevy_progress_tracking::{ProgressPlugin, ProgressRemotePlugin};
+/// # struct Loading;
+/// App::new().add_plugins((
+///     RemotePlugin::default(),
+///     ProgressPlugin::<Loading>::new(),
+///     ProgressRemotePlugin,
+/// ));
+/// ```
+pub struct ProgressRemotePlugin;
+
+impl Plugin for ProgressRemotePlugin {
+    fn build(&self, _app: &mut App) {}
+
+    // The remote plugin replaces all methods when it is built, so the method is added once all plugins are built
+    fn finish(&self, app: &mut App) {
+        let system = app.world_mut().register_system(list_trackers);
+        let Some(mut methods) = app.world_mut().get_resource_mut::<RemoteMethods>() else {
+            warn!("Add the RemotePlugin to query trackers over the Bevy Remote Protocol");
+            return;
+        };
+        methods.insert(BRP_PROGRESS_METHOD, RemoteMethodSystemId::Instant(system));
+    }
+}
+
+fn list_trackers(In(_): In<Option<Value>>, world: &World) -> BrpResult {
+    let Some(registry) = world.get_resource::<ProgressRegistry>() else {
+        return Ok(json!([]));
+    };
+    let trackers = registry
+        .iter()
+        .filter_map(|tracker| {
+            let count = tracker.count(world)?;
+            Some(json!({
+                "name": tracker.name(),
+                "done": count.done,
+                "total": count.total,
+                "progress": tracker.ratio(world)?,
+                "pending_tasks": tracker.pending_tasks(world)?,
+            }))
+        })
+        .collect();
+    Ok(Value::Array(trackers))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::Update;
+    use bevy::ecs::system::ResMut;
+    use bevy::remote::{RemoteMethodSystemId, RemoteMethods, RemotePlugin};
+    use serde_json::json;
+
+    use crate::testing::{self, Loading};
+    use crate::{Progress, ProgressRemotePlugin, BRP_PROGRESS_METHOD};
+
+    #[test]
+    fn lists_trackers_over_remote_protocol() {
+        let mut app = testing::app();
+        app.add_plugins((ProgressRemotePlugin, RemotePlugin::default()))
+            .add_systems(Update, |mut progress: ResMut<Progress<Loading>>| {
+                progress.track(2, 1);
+            });
+        app.world_mut()
+            .resource_mut::<Progress<Loading>>()
+            .add_named_task("spawn player");
+        app.finish();
+        app.update();
+
+        let Some(&RemoteMethodSystemId::Instant(system)) = app
+            .world()
+            .resource::<RemoteMethods>()
+            .get(BRP_PROGRESS_METHOD)
+        else {
+            panic!("The progress method is not registered");
+        };
+        let response = app.world_mut().run_system_with(system, None).unwrap();
+        assert_eq!(
+            response.unwrap(),
+            json!([{
+                "name": "bevy_progress_tracking::testing::Loading",
+                "done": 1,
+                "total": 3,
+                "progress": 1f32 / 3.,
+                "pending_tasks": ["spawn player"],
+            }])
+        );
+    }
+}
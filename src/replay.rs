@@ -0,0 +1,268 @@
+use std::fmt::Write as _;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use bevy::app::{App, AppExit, Plugin, Update};
+use bevy::ecs::event::EventReader;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Local, Res, ResMut};
+use bevy::log::warn;
+
+use crate::file::write_atomic;
+use crate::plugin::with_tracker_schedules;
+use crate::{Progress, ProgressCount, ProgressMarker, ProgressSystems};
+
+/// Plugin recording the counts of the tracker `T` in every frame to a file
+///
+/// The recording is written and stops once all tasks are done, or when the app exits before that.
+/// Replay it with the [`ReplayProgressPlugin`] to work on a loading screen without loading anything.
+/// ```edition2021,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{ProgressPlugin, RecordProgressPlugin};
+/// # struct Loading;
+/// App::new().add_plugins((
+///     ProgressPlugin::<Loading>::new(),
+///     RecordProgressPlugin::<Loading>::new("loading.progress"),
+/// ));
+/// ```
+pub struct RecordProgressPlugin<T> {
+    path: PathBuf,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> RecordProgressPlugin<T> {
+    /// Create a plugin recording to the file at the given path
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        RecordProgressPlugin {
+            path: path.into(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ProgressMarker> Plugin for RecordProgressPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ProgressRecording::<T> {
+            path: self.path.clone(),
+            samples: Vec::new(),
+            _marker: PhantomData,
+        });
+        with_tracker_schedules::<T>(app, |app, schedules| {
+            app.add_systems(
+                schedules.finish,
+                record_progress::<T>.after(ProgressSystems::FinishFrame),
+            );
+        });
+    }
+}
+
+/// Resource of the samples recorded by the [`RecordProgressPlugin`]
+#[derive(Resource)]
+pub struct ProgressRecording<T> {
+    path: PathBuf,
+    samples: Vec<ProgressCount>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ProgressRecording<T> {
+    /// Returns the counts of all recorded frames
+    pub fn samples(&self) -> &[ProgressCount] {
+        &self.samples
+    }
+
+    /// Write the recorded samples to the file
+    pub fn save(&self) -> io::Result<()> {
+        let mut content = String::new();
+        for sample in &self.samples {
+            let _ = writeln!(content, "{} {}", sample.done, sample.total);
+        }
+        write_atomic(&self.path, content)
+    }
+}
+
+fn record_progress<T: ProgressMarker>(
+    progress: Res<Progress<T>>,
+    mut recording: ResMut<ProgressRecording<T>>,
+    mut exit: EventReader<AppExit>,
+    mut saved: Local<bool>,
+) {
+    if *saved {
+        return;
+    }
+    let count = progress.count();
+    recording.samples.push(count);
+    let completed = count.total > 0 && count.is_complete();
+    if exit.read().count() == 0 && !completed {
+        return;
+    }
+    *saved = true;
+    if let Err(error) = recording.save() {
+        warn!(
+            "Failed to save progress recording to '{}': {}",
+            recording.path.display(),
+            error
+        );
+    }
+}
+
+/// Plugin replaying a recording of the [`RecordProgressPlugin`] as the progress of the tracker `T`
+///
+/// Every frame tracks the recorded sample of the same frame as the source `"replay"`.
+/// After the last sample, the last sample is tracked in every frame.
+/// This is meant for development builds to iterate on loading screens deterministically.
+pub struct ReplayProgressPlugin<T> {
+    path: PathBuf,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ReplayProgressPlugin<T> {
+    /// Create a plugin replaying the recording at the given path
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        ReplayProgressPlugin {
+            path: path.into(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ProgressMarker> Plugin for ReplayProgressPlugin<T> {
+    fn build(&self, app: &mut App) {
+        let samples = match load_samples(&self.path) {
+            Ok(samples) => samples,
+            Err(error) => {
+                warn!(
+                    "Failed to load progress recording from '{}': {}",
+                    self.path.display(),
+                    error
+                );
+                return;
+            }
+        };
+        app.add_systems(
+            Update,
+            move |mut progress: ResMut<Progress<T>>, mut frame: Local<usize>| {
+                let Some(sample) = samples.get(*frame).or(samples.last()) else {
+                    return;
+                };
+                progress.track_source("replay", *sample);
+                *frame += 1;
+            },
+        );
+    }
+}
+
+fn load_samples(path: &Path) -> io::Result<Vec<ProgressCount>> {
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            parse_sample(line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid sample '{line}'"),
+                )
+            })
+        })
+        .collect()
+}
+
+fn parse_sample(line: &str) -> Option<ProgressCount> {
+    let (done, total) = line.split_once(' ')?;
+    let count = ProgressCount::new(done.parse().ok()?, total.parse().ok()?);
+    (count.done <= count.total).then_some(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use bevy::app::{App, Update};
+    use bevy::ecs::schedule::ScheduleLabel;
+    use bevy::ecs::system::{Local, ResMut};
+
+    use crate::{
+        Progress, ProgressCount, ProgressPlugin, ProgressRecording, RecordProgressPlugin,
+        ReplayProgressPlugin,
+    };
+
+    struct Loading;
+
+    #[test]
+    fn replays_recorded_frames() {
+        let path = std::env::temp_dir().join("bevy_progress_tracking_replay_test.progress");
+        let mut app = App::new();
+        app.add_plugins((
+            ProgressPlugin::<Loading>::new(),
+            RecordProgressPlugin::<Loading>::new(&path),
+        ))
+        .add_systems(
+            Update,
+            |mut progress: ResMut<Progress<Loading>>, mut frame: Local<usize>| {
+                *frame += 1;
+                progress.track(2, (*frame).min(2));
+            },
+        );
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(
+            app.world()
+                .resource::<ProgressRecording<Loading>>()
+                .samples(),
+            [ProgressCount::new(1, 2), ProgressCount::done(2)]
+        );
+
+        let mut replay = App::new();
+        replay.add_plugins((
+            ProgressPlugin::<Loading>::new(),
+            ReplayProgressPlugin::<Loading>::new(&path),
+        ));
+        fs::remove_file(&path).unwrap();
+        let mut counts = Vec::new();
+        for _ in 0..3 {
+            replay.update();
+            counts.push(replay.world().resource::<Progress<Loading>>().count());
+        }
+        assert_eq!(
+            counts,
+            [
+                ProgressCount::new(1, 2),
+                ProgressCount::done(2),
+                ProgressCount::done(2)
+            ]
+        );
+    }
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct FinishLoading;
+
+    #[test]
+    fn records_in_the_finish_schedule() {
+        let path = std::env::temp_dir().join("bevy_progress_tracking_schedule_test.progress");
+        let mut app = App::new();
+        // The recorder is added before the tracker configured its schedule
+        app.add_plugins((
+            RecordProgressPlugin::<Loading>::new(&path),
+            ProgressPlugin::<Loading>::new().finish_frame_in(FinishLoading),
+        ))
+        .add_systems(Update, |mut progress: ResMut<Progress<Loading>>| {
+            progress.track(2, 1);
+        });
+        app.update();
+        assert!(app
+            .world()
+            .resource::<ProgressRecording<Loading>>()
+            .samples()
+            .is_empty());
+
+        app.world_mut().run_schedule(FinishLoading);
+        assert_eq!(
+            app.world()
+                .resource::<ProgressRecording<Loading>>()
+                .samples(),
+            [ProgressCount::new(1, 2)]
+        );
+    }
+}
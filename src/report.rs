@@ -0,0 +1,659 @@
+use std::any::type_name;
+use std::fmt::Write as _;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::event::{Event, EventWriter};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Local, Res};
+use bevy::log::warn;
+
+use crate::file::write_atomic;
+use crate::plugin::with_tracker_schedules;
+use crate::{Progress, ProgressClock, ProgressMarker, ProgressSystems, TaskId};
+
+/// Event with the timings of a completed loading session
+///
+/// A session starts in the first frame the tracker has tasks and ends in the first frame all of them
+/// are done. A new session starts once the tracker has tasks that are not done again.
+/// Sent by the [`SessionReportPlugin`].
+#[derive(Event, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "report_json", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct LoadingSessionReport {
+    /// Type name of the tracker marker
+    pub tracker: String,
+    /// Time from the start of the session until all tasks were done
+    pub duration: Duration,
+    /// Number of tasks when the session was completed
+    pub total: usize,
+    /// Time from the start of the session until each source was complete
+    ///
+    /// See [`Progress::contributions`].
+    pub sources: Vec<(String, Duration)>,
+    /// Time from the start of the session until each named task was done
+    pub tasks: Vec<(String, Duration)>,
+    /// Number of times no task was done for longer than the stall threshold
+    pub stalls: usize,
+    /// When each source and named task started and finished, relative to the start of the session
+    ///
+    /// Sources start in the first frame they report progress, named tasks in the first frame they exist.
+    pub timeline: Vec<TimelineEntry>,
+    /// Memory usage in bytes when the session was completed
+    ///
+    /// Only sampled with [`SessionReportPlugin::with_memory_sampling`] or [`SessionReportPlugin::with_memory_sampler`].
+    pub memory: Option<u64>,
+}
+
+/// Start and end of a source or named task in a [`LoadingSessionReport`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "report_json", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct TimelineEntry {
+    /// Name of the source or label of the named task
+    pub name: String,
+    /// `true` for named tasks and `false` for sources
+    pub named_task: bool,
+    /// Time from the start of the session until the source or task started
+    pub start: Duration,
+    /// Time from the start of the session until the source or task was done
+    pub end: Duration,
+    /// Memory usage in bytes in the frame the source or task was done
+    ///
+    /// Comparing it to the previous entries attributes memory spikes to the source or task.
+    pub memory: Option<u64>,
+}
+
+impl LoadingSessionReport {
+    /// Create a report without timings of sources and named tasks
+    ///
+    /// Use this to build baselines by hand; fields can be set afterwards.
+    pub fn new(tracker: impl Into<String>, duration: Duration, total: usize) -> Self {
+        LoadingSessionReport {
+            tracker: tracker.into(),
+            duration,
+            total,
+            sources: Vec::new(),
+            tasks: Vec::new(),
+            stalls: 0,
+            timeline: Vec::new(),
+            memory: None,
+        }
+    }
+
+    /// Returns the timing of the source with the given name
+    pub fn source(&self, name: &str) -> Option<Duration> {
+        self.sources
+            .iter()
+            .find(|(source, _)| source == name)
+            .map(|(_, duration)| *duration)
+    }
+
+    /// Compare the report to a baseline, returning all timings slower than allowed by the tolerance
+    ///
+    /// A tolerance of `1.25` allows every timing to take up to 25% longer than in the baseline.
+    /// The duration of the whole session is compared as well as the timings of all sources
+    /// that are part of both reports.
+    /// ```edition2021
+    /// # use std::time::Duration;
+    /// # use bevy_progress_tracking::LoadingSessionReport;
+    /// let mut baseline = LoadingSessionReport::new("Loading", Duration::from_secs(2), 10);
+    /// baseline.sources.push(("map".to_owned(), Duration::from_secs(2)));
+    /// let mut current = baseline.clone();
+    /// current.sources[0].1 = Duration::from_secs(3);
+    ///
+    /// let regressions = current.regressions(&baseline, 1.25);
+    /// assert_eq!(regressions[0].source.as_deref(), Some("map"));
+    /// ```
+    pub fn regressions(
+        &self,
+        baseline: &LoadingSessionReport,
+        tolerance: f32,
+    ) -> Vec<SessionRegression> {
+        let slower = |current: Duration, baseline: Duration| {
+            current.as_secs_f32() > baseline.as_secs_f32() * tolerance
+        };
+        let mut regressions = Vec::new();
+        if slower(self.duration, baseline.duration) {
+            regressions.push(SessionRegression {
+                source: None,
+                baseline: baseline.duration,
+                current: self.duration,
+            });
+        }
+        for (source, current) in &self.sources {
+            let Some(previous) = baseline.source(source) else {
+                continue;
+            };
+            if slower(*current, previous) {
+                regressions.push(SessionRegression {
+                    source: Some(source.clone()),
+                    baseline: previous,
+                    current: *current,
+                });
+            }
+        }
+        regressions
+    }
+
+    /// Compare the timings of the report to another session, for example with a different load order
+    ///
+    /// The first diff is the duration of the whole session, followed by the sources of this report and then the
+    /// sources only the other report has. Timings missing in one of the reports are `None`.
+    /// ```edition2021
+    /// # use std::time::Duration;
+    /// # use bevy_progress_tracking::LoadingSessionReport;
+    /// let mut before = LoadingSessionReport::new("Loading", Duration::from_secs(3), 10);
+    /// before.sources.push(("map".to_owned(), Duration::from_secs(3)));
+    /// let mut after = LoadingSessionReport::new("Loading", Duration::from_secs(2), 10);
+    /// after.sources.push(("map".to_owned(), Duration::from_secs(1)));
+    ///
+    /// let diffs = before.compare(&after);
+    /// assert_eq!(diffs[0].change_secs(), Some(-1.));
+    /// assert_eq!(diffs[1].source.as_deref(), Some("map"));
+    /// ```
+    pub fn compare(&self, other: &LoadingSessionReport) -> Vec<TimingDiff> {
+        let mut diffs = vec![TimingDiff {
+            source: None,
+            before: Some(self.duration),
+            after: Some(other.duration),
+        }];
+        for (source, before) in &self.sources {
+            diffs.push(TimingDiff {
+                source: Some(source.clone()),
+                before: Some(*before),
+                after: other.source(source),
+            });
+        }
+        for (source, after) in &other.sources {
+            if self.source(source).is_none() {
+                diffs.push(TimingDiff {
+                    source: Some(source.clone()),
+                    before: None,
+                    after: Some(*after),
+                });
+            }
+        }
+        diffs
+    }
+
+    /// Returns the timeline of the session in the Trace Event Format of `chrome://tracing` and Perfetto
+    ///
+    /// The session and every entry of the [timeline](LoadingSessionReport::timeline) are shown on their own row.
+    pub fn to_chrome_trace(&self) -> String {
+        let mut trace = String::from("{\"traceEvents\":[");
+        let session = TimelineEntry {
+            name: self.tracker.clone(),
+            named_task: false,
+            start: Duration::ZERO,
+            end: self.duration,
+            memory: self.memory,
+        };
+        for (row, entry) in std::iter::once(&session).chain(&self.timeline).enumerate() {
+            if row > 0 {
+                trace.push(',');
+            }
+            let category = match (row, entry.named_task) {
+                (0, _) => "session",
+                (_, true) => "named task",
+                (_, false) => "source",
+            };
+            let _ = write!(
+                trace,
+                "{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":0,\"tid\":{}}}",
+                escape_json(&entry.name),
+                category,
+                entry.start.as_micros(),
+                entry.end.saturating_sub(entry.start).as_micros(),
+                row
+            );
+        }
+        trace.push_str("]}");
+        trace
+    }
+
+    /// Parse a report from JSON
+    ///
+    /// Only available with the `report_json` feature.
+    #[cfg(feature = "report_json")]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Returns the resident memory of the process in bytes
+///
+/// This is the default sampler of [`SessionReportPlugin::with_memory_sampling`].
+/// Memory is only sampled on Linux; other platforms return `None`.
+pub fn resident_memory() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let line = status
+            .lines()
+            .find_map(|line| line.strip_prefix("VmRSS:"))?;
+        let kilobytes: u64 = line.trim().strip_suffix("kB")?.trim().parse().ok()?;
+        Some(kilobytes * 1024)
+    }
+    #[cfg(not(target_os = "linux"))]
+    None
+}
+
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            character if character.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", character as u32);
+            }
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Event sent when a timing of a loading session is slower than in the baseline
+///
+/// See [`SessionReportPlugin::with_baseline`].
+#[derive(Event, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct SessionRegression {
+    /// Source with the slower timing or `None` for the duration of the whole session
+    pub source: Option<String>,
+    /// Timing in the baseline
+    pub baseline: Duration,
+    /// Timing in the current session
+    pub current: Duration,
+}
+
+/// Difference of a timing between two sessions
+///
+/// See [`LoadingSessionReport::compare`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct TimingDiff {
+    /// Source of the timing or `None` for the duration of the whole session
+    pub source: Option<String>,
+    /// Timing in the report [`compare`](LoadingSessionReport::compare) was called on
+    pub before: Option<Duration>,
+    /// Timing in the other report
+    pub after: Option<Duration>,
+}
+
+impl TimingDiff {
+    /// Returns how many seconds the timing changed, negative if the other session was faster
+    ///
+    /// `None` if the timing is missing in one of the sessions.
+    pub fn change_secs(&self) -> Option<f32> {
+        Some(self.after?.as_secs_f32() - self.before?.as_secs_f32())
+    }
+}
+
+/// Plugin sending a [`LoadingSessionReport`] for every completed loading session of the tracker `T`
+///
+/// Timings are taken from the [`ProgressClock`], so time in which the clock was paused,
+/// like while a browser tab was hidden, neither counts as a stall nor towards any duration.
+/// With the `report_json` feature, reports can also be written to a JSON file.
+/// ```edition2021
+/// # use std::time::Duration;
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{LoadingSessionReport, ProgressPlugin, SessionReportPlugin};
+/// # struct Loading;
+/// fn log_reports(mut reports: EventReader<LoadingSessionReport>) {
+///     for report in reports.read() {
+///         println!("Loading took {:?} with {} stalls", report.duration, report.stalls);
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins((
+///         ProgressPlugin::<Loading>::new(),
+///         SessionReportPlugin::<Loading>::new().with_stall_threshold(Duration::from_secs(2)),
+///     ))
+///     .add_systems(Update, log_reports);
+/// ```
+pub struct SessionReportPlugin<T> {
+    stall_threshold: Duration,
+    baseline: Option<(LoadingSessionReport, f32)>,
+    chrome_trace: Option<PathBuf>,
+    memory_sampler: Option<fn() -> Option<u64>>,
+    #[cfg(feature = "report_json")]
+    json: Option<PathBuf>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> SessionReportPlugin<T> {
+    /// Create a plugin counting stalls of more than one second
+    pub fn new() -> Self {
+        SessionReportPlugin {
+            stall_threshold: Duration::from_secs(1),
+            baseline: None,
+            chrome_trace: None,
+            memory_sampler: None,
+            #[cfg(feature = "report_json")]
+            json: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set how long no task has to be done to count as a stall
+    pub fn with_stall_threshold(mut self, stall_threshold: Duration) -> Self {
+        self.stall_threshold = stall_threshold;
+        self
+    }
+
+    /// Compare every report to the given baseline and send a [`SessionRegression`] for slower timings
+    ///
+    /// Regressions are also logged as warnings. See [`LoadingSessionReport::regressions`] for the tolerance.
+    #[cfg_attr(
+        feature = "report_json",
+        doc = "With the `report_json` feature, baselines can be loaded with [`LoadingSessionReport::from_json`]."
+    )]
+    #[cfg_attr(
+        not(feature = "report_json"),
+        doc = "With the `report_json` feature, baselines can be loaded with `LoadingSessionReport::from_json`."
+    )]
+    pub fn with_baseline(mut self, baseline: LoadingSessionReport, tolerance: f32) -> Self {
+        self.baseline = Some((baseline, tolerance));
+        self
+    }
+
+    /// Write the timeline of every report to a file at the given path
+    ///
+    /// The file can be opened in `chrome://tracing` or Perfetto. See [`LoadingSessionReport::to_chrome_trace`].
+    pub fn with_chrome_trace(mut self, path: impl Into<PathBuf>) -> Self {
+        self.chrome_trace = Some(path.into());
+        self
+    }
+
+    /// Sample the resident memory of the process whenever a source or named task is done
+    ///
+    /// The samples are part of the [timeline](LoadingSessionReport::timeline). See [`resident_memory`].
+    pub fn with_memory_sampling(self) -> Self {
+        self.with_memory_sampler(resident_memory)
+    }
+
+    /// Sample memory with the given function whenever a source or named task is done
+    ///
+    /// Use this to sample other memory, like the memory used by the GPU.
+    pub fn with_memory_sampler(mut self, sampler: fn() -> Option<u64>) -> Self {
+        self.memory_sampler = Some(sampler);
+        self
+    }
+
+    /// Write every report to a JSON file at the given path
+    ///
+    /// Only available with the `report_json` feature.
+    #[cfg(feature = "report_json")]
+    pub fn with_json(mut self, path: impl Into<PathBuf>) -> Self {
+        self.json = Some(path.into());
+        self
+    }
+}
+
+impl<T> Default for SessionReportPlugin<T> {
+    fn default() -> Self {
+        SessionReportPlugin::new()
+    }
+}
+
+impl<T: ProgressMarker> Plugin for SessionReportPlugin<T> {
+    fn build(&self, app: &mut App) {
+        let stall_threshold = self.stall_threshold;
+        #[cfg(feature = "report_json")]
+        let json = self.json.clone();
+        let baseline = self.baseline.clone();
+        let chrome_trace = self.chrome_trace.clone();
+        let memory_sampler = self.memory_sampler.unwrap_or(|| None);
+        app.add_event::<LoadingSessionReport>()
+            .add_event::<SessionRegression>();
+        let report = move |progress: Res<Progress<T>>,
+                           clock: Res<ProgressClock>,
+                           mut session: Local<Session>,
+                           mut reports: EventWriter<LoadingSessionReport>,
+                           mut regressions: EventWriter<SessionRegression>| {
+            let Some(report) =
+                session.update(&progress, clock.elapsed(), stall_threshold, memory_sampler)
+            else {
+                return;
+            };
+            #[cfg(feature = "report_json")]
+            if let Some(path) = json.as_ref() {
+                let written = serde_json::to_string_pretty(&report)
+                    .map_err(std::io::Error::from)
+                    .and_then(|content| write_atomic(path, content));
+                if let Err(error) = written {
+                    warn!(
+                        "Failed to write loading session report to '{}': {}",
+                        path.display(),
+                        error
+                    );
+                }
+            }
+            if let Some(path) = chrome_trace.as_ref() {
+                if let Err(error) = write_atomic(path, report.to_chrome_trace()) {
+                    warn!(
+                        "Failed to write loading session trace to '{}': {}",
+                        path.display(),
+                        error
+                    );
+                }
+            }
+            if let Some((baseline, tolerance)) = baseline.as_ref() {
+                for regression in report.regressions(baseline, *tolerance) {
+                    warn!(
+                        "Loading '{}' took {:?} instead of {:?}",
+                        regression.source.as_deref().unwrap_or(&report.tracker),
+                        regression.current,
+                        regression.baseline
+                    );
+                    regressions.write(regression);
+                }
+            }
+            reports.write(report);
+        };
+        with_tracker_schedules::<T>(app, |app, schedules| {
+            app.add_systems(schedules.finish, report.after(ProgressSystems::FinishFrame));
+        });
+    }
+}
+
+#[derive(Default)]
+struct Session {
+    running: Option<RunningSession>,
+}
+
+struct RunningSession {
+    started: Duration,
+    last_done: (usize, Duration),
+    stalled: bool,
+    stalls: usize,
+    sources: Vec<(&'static str, Duration, Option<Done>)>,
+    tasks: Vec<(TaskId, String, Duration, Option<Done>)>,
+}
+
+/// Time and sampled memory when a source or named task was done
+type Done = (Duration, Option<u64>);
+
+impl Session {
+    fn update<T>(
+        &mut self,
+        progress: &Progress<T>,
+        now: Duration,
+        stall_threshold: Duration,
+        memory: fn() -> Option<u64>,
+    ) -> Option<LoadingSessionReport> {
+        let count = progress.count();
+        if count.total == 0 || (self.running.is_none() && count.is_complete()) {
+            return None;
+        }
+        let session = self.running.get_or_insert_with(|| RunningSession {
+            started: now,
+            last_done: (count.done, now),
+            stalled: false,
+            stalls: 0,
+            sources: Vec::new(),
+            tasks: Vec::new(),
+        });
+        let elapsed = now - session.started;
+        if count.done != session.last_done.0 {
+            session.last_done = (count.done, now);
+            session.stalled = false;
+        } else if !session.stalled && now - session.last_done.1 > stall_threshold {
+            session.stalled = true;
+            session.stalls += 1;
+        }
+        // Memory is sampled at most once per frame
+        let mut sample = None;
+        let mut completed =
+            |done: bool| done.then(|| (elapsed, *sample.get_or_insert_with(memory)));
+        for &(source, source_count) in progress.contributions() {
+            match session
+                .sources
+                .iter_mut()
+                .find(|(name, _, _)| *name == source)
+            {
+                Some((_, _, done @ None)) => *done = completed(source_count.is_complete()),
+                Some(_) => {}
+                None => {
+                    let done = completed(source_count.is_complete());
+                    session.sources.push((source, elapsed, done));
+                }
+            }
+        }
+        for (id, label, done) in progress.named_tasks() {
+            match session.tasks.iter_mut().find(|(task, ..)| *task == id) {
+                Some((.., end @ None)) => *end = completed(done),
+                Some(_) => {}
+                None => {
+                    let end = completed(done);
+                    session.tasks.push((id, label.to_owned(), elapsed, end));
+                }
+            }
+        }
+        if !count.is_complete() {
+            return None;
+        }
+        let memory = *sample.get_or_insert_with(memory);
+        let session = self.running.take()?;
+        let end = |done: Option<Done>| done.unwrap_or((elapsed, memory));
+        Some(LoadingSessionReport {
+            tracker: type_name::<T>().to_owned(),
+            duration: elapsed,
+            total: count.total,
+            sources: session
+                .sources
+                .iter()
+                .map(|(source, _, done)| (source.to_string(), end(*done).0))
+                .collect(),
+            tasks: session
+                .tasks
+                .iter()
+                .filter_map(|(_, label, _, done)| Some((label.clone(), (*done)?.0)))
+                .collect(),
+            stalls: session.stalls,
+            timeline: session
+                .sources
+                .into_iter()
+                .map(|(source, start, done)| TimelineEntry {
+                    name: source.to_owned(),
+                    named_task: false,
+                    start,
+                    end: end(done).0,
+                    memory: end(done).1,
+                })
+                .chain(
+                    session
+                        .tasks
+                        .into_iter()
+                        .map(|(_, label, start, done)| TimelineEntry {
+                            name: label,
+                            named_task: true,
+                            start,
+                            end: end(done).0,
+                            memory: end(done).1,
+                        }),
+                )
+                .collect(),
+            memory,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::report::Session;
+    use crate::{LoadingSessionReport, Progress, ProgressCount};
+
+    struct Loading;
+
+    #[test]
+    fn compares_sessions_per_source() {
+        let mut before = LoadingSessionReport::new("Loading", Duration::from_secs(4), 3);
+        before.sources = vec![
+            ("map".to_owned(), Duration::from_secs(4)),
+            ("audio".to_owned(), Duration::from_secs(1)),
+        ];
+        let mut after = LoadingSessionReport::new("Loading", Duration::from_secs(5), 3);
+        after.sources = vec![
+            ("map".to_owned(), Duration::from_secs(2)),
+            ("shaders".to_owned(), Duration::from_secs(5)),
+        ];
+
+        let diffs: Vec<_> = before
+            .compare(&after)
+            .iter()
+            .map(|diff| (diff.source.clone(), diff.change_secs()))
+            .collect();
+        assert_eq!(
+            diffs,
+            [
+                (None, Some(1.)),
+                (Some("map".to_owned()), Some(-2.)),
+                (Some("audio".to_owned()), None),
+                (Some("shaders".to_owned()), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_source_timings_and_stalls() {
+        let mut progress = Progress::<Loading>::default();
+        let mut session = Session::default();
+        let threshold = Duration::from_secs(1);
+        let mut frame = |progress: &mut Progress<Loading>, second: u64, map: usize| {
+            progress.track_source("map", ProgressCount::new(map, 2));
+            progress.track_source("audio", ProgressCount::done(1));
+            progress.finish_frame();
+            session.update(progress, Duration::from_secs(second), threshold, || {
+                Some(64)
+            })
+        };
+
+        assert_eq!(frame(&mut progress, 1, 0), None);
+        assert_eq!(frame(&mut progress, 2, 1), None);
+        assert_eq!(frame(&mut progress, 5, 1), None);
+        let report = frame(&mut progress, 6, 2).unwrap();
+        assert_eq!(report.duration, Duration::from_secs(5));
+        assert_eq!(report.stalls, 1);
+        assert_eq!(report.source("audio"), Some(Duration::ZERO));
+        assert_eq!(report.source("map"), Some(Duration::from_secs(5)));
+        assert_eq!(report.memory, Some(64));
+        assert!(report.timeline.iter().all(|entry| entry.memory == Some(64)));
+        assert_eq!(
+            report.to_chrome_trace(),
+            format!(
+                "{{\"traceEvents\":[{},{},{}]}}",
+                r#"{"name":"bevy_progress_tracking::report::tests::Loading","cat":"session","ph":"X","ts":0,"dur":5000000,"pid":0,"tid":0}"#,
+                r#"{"name":"map","cat":"source","ph":"X","ts":0,"dur":5000000,"pid":0,"tid":1}"#,
+                r#"{"name":"audio","cat":"source","ph":"X","ts":0,"dur":0,"pid":0,"tid":2}"#
+            )
+        );
+    }
+}
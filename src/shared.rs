@@ -0,0 +1,136 @@
+use std::marker::PhantomData;
+use std::mem;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use bevy::ecs::resource::Resource;
+use bevy::ecs::world::World;
+
+use crate::{ProgressCount, ProgressMarker, Task};
+
+/// Resource to track progress of the tracker `T` from systems running in parallel
+///
+/// Tracking progress through [`Progress`](crate::Progress) requires mutable access to the resource,
+/// so all systems doing so cannot run at the same time. This resource only needs shared access.
+/// Its counts are added to the [`Progress`](crate::Progress) resource when the frame is finished.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{SharedProgress, Task};
+/// # struct Loading;
+/// # #[derive(Component)]
+/// # struct Chunk { generated: bool }
+/// fn track_chunks(chunks: Query<&Chunk>, progress: Res<SharedProgress<Loading>>) {
+///     for chunk in &chunks {
+///         progress.task(if chunk.generated { Task::Done } else { Task::InProgress });
+///     }
+/// }
+/// ```
+#[derive(Resource)]
+pub struct SharedProgress<T> {
+    count: Mutex<ProgressCount>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for SharedProgress<T> {
+    fn default() -> Self {
+        SharedProgress {
+            count: Mutex::new(ProgressCount::default()),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> SharedProgress<T> {
+    /// track the given amount of tasks of which some can already be completed
+    pub fn track(&self, tasks: usize, done: usize) {
+        *self.count() += ProgressCount::new(done, tasks);
+    }
+
+    /// Track the tasks of the given count
+    pub fn track_count(&self, count: ProgressCount) {
+        self.track(count.total, count.done);
+    }
+
+    /// Convenience function to track a single task
+    pub fn task(&self, task: Task) {
+        if task == Task::Done {
+            self.track(1, 1);
+        } else {
+            self.track(1, 0);
+        }
+    }
+
+    pub(crate) fn take(&self) -> ProgressCount {
+        mem::take(&mut *self.count())
+    }
+
+    // Done and total tasks are only changed together, so a frame never sees half of a `track` call
+    fn count(&self) -> MutexGuard<'_, ProgressCount> {
+        self.count.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Moves the progress tracked in the sub-app world to the [`SharedProgress`] of the main world
+pub(crate) fn mirror_shared_progress<T: ProgressMarker>(
+    main_world: &mut World,
+    sub_world: &mut World,
+) {
+    let count = sub_world.resource::<SharedProgress<T>>().take();
+    main_world
+        .resource::<SharedProgress<T>>()
+        .track_count(count);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use bevy::app::{AppLabel, SubApp};
+
+    use crate::testing::{self, Loading};
+    use crate::{ProgressAppExt, ProgressCount, SharedProgress, Task};
+
+    #[test]
+    fn take_resets_counts() {
+        let shared = SharedProgress::<Loading>::default();
+        shared.task(Task::Done);
+        shared.track(2, 0);
+        assert_eq!(shared.take(), ProgressCount::new(1, 3));
+        assert_eq!(shared.take(), ProgressCount::default());
+    }
+
+    #[test]
+    fn take_never_splits_tracked_tasks() {
+        let shared = SharedProgress::<Loading>::default();
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                for _ in 0..10_000 {
+                    shared.track(1, 1);
+                }
+            });
+            let mut taken = ProgressCount::default();
+            while taken.total < 10_000 {
+                let count = shared.take();
+                assert_eq!(count.done, count.total);
+                taken += count;
+            }
+        });
+    }
+
+    #[test]
+    fn mirrors_sub_app_progress() {
+        #[derive(AppLabel, Debug, Clone, PartialEq, Eq, Hash)]
+        struct RenderLike;
+
+        let mut app = testing::app();
+        app.insert_sub_app(RenderLike, SubApp::new());
+        app.mirror_sub_app_progress::<Loading>(RenderLike);
+        app.sub_app(RenderLike)
+            .world()
+            .resource::<SharedProgress<Loading>>()
+            .track(2, 1);
+
+        app.update();
+        app.update();
+        assert_eq!(testing::count(&app), ProgressCount::new(1, 2));
+    }
+}
@@ -0,0 +1,116 @@
+use std::any::type_name;
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Local, Res};
+use bevy::log::info_span;
+use bevy::log::tracing::Span;
+
+use crate::plugin::with_tracker_schedules;
+use crate::{Progress, ProgressMarker, ProgressSystems, TaskId};
+
+/// Plugin opening `tracing` spans for the loading sessions and named tasks of the tracker `T`
+///
+/// A `loading session` span is opened once the tracker has tasks that are not done and closed when all
+/// of them are done. Every named task gets a `named task` span in the session span that is closed
+/// when the task is done or removed. The spans are kept open across frames, so subscribers show
+/// their whole lifetime rather than the time they were entered.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{ProgressPlugin, ProgressSpansPlugin};
+/// # struct Loading;
+/// App::new().add_plugins((
+///     ProgressPlugin::<Loading>::new(),
+///     ProgressSpansPlugin::<Loading>::new(),
+/// ));
+/// ```
+pub struct ProgressSpansPlugin<T> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ProgressSpansPlugin<T> {
+    /// Create a new plugin for the tracker with the marker `T`
+    pub fn new() -> Self {
+        ProgressSpansPlugin {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for ProgressSpansPlugin<T> {
+    fn default() -> Self {
+        ProgressSpansPlugin::new()
+    }
+}
+
+impl<T: ProgressMarker> Plugin for ProgressSpansPlugin<T> {
+    fn build(&self, app: &mut App) {
+        with_tracker_schedules::<T>(app, |app, schedules| {
+            app.add_systems(
+                schedules.finish,
+                (|progress: Res<Progress<T>>, mut spans: Local<Spans>| spans.update(&progress))
+                    .after(ProgressSystems::FinishFrame),
+            );
+        });
+    }
+}
+
+#[derive(Default)]
+struct Spans {
+    session: Option<Span>,
+    tasks: Vec<(TaskId, Span)>,
+}
+
+impl Spans {
+    fn update<T>(&mut self, progress: &Progress<T>) {
+        let count = progress.count();
+        if count.is_complete() {
+            self.tasks.clear();
+            self.session = None;
+            return;
+        }
+        let session = self
+            .session
+            .get_or_insert_with(|| info_span!("loading session", tracker = type_name::<T>()));
+        self.tasks
+            .retain(|(id, _)| progress.named_task(*id).is_some_and(|(_, done)| !done));
+        for (id, label, done) in progress.named_tasks() {
+            if !done && !self.tasks.iter().any(|(task, _)| *task == id) {
+                let span = info_span!(parent: &*session, "named task", label);
+                self.tasks.push((id, span));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spans::Spans;
+    use crate::Progress;
+
+    struct Loading;
+
+    #[test]
+    fn closes_spans_of_done_tasks() {
+        let mut progress = Progress::<Loading>::default();
+        let mut spans = Spans::default();
+        let terrain = progress.add_named_task("terrain");
+        progress.add_named_task("trees");
+        progress.finish_frame();
+        spans.update(&progress);
+        assert!(spans.session.is_some());
+        assert_eq!(spans.tasks.len(), 2);
+
+        progress.complete_named_task(terrain);
+        progress.finish_frame();
+        spans.update(&progress);
+        assert_eq!(spans.tasks.len(), 1);
+
+        progress.clear();
+        progress.finish_frame();
+        spans.update(&progress);
+        assert!(spans.session.is_none());
+        assert!(spans.tasks.is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use bevy::app::{App, First, Plugin, PostStartup};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Local, ResMut};
+use bevy::platform::time::Instant;
+
+use crate::{Progress, ProgressPlugin};
+
+/// Marker of the tracker counting the startup of the app
+pub struct AppStartup;
+
+/// Opt-in plugin tracking the startup of the app in [`Progress<AppStartup>`]
+///
+/// Startup consists of three tasks:
+/// 1. all plugins were built and are finished, which is known once the first frame starts in [`First`]
+/// 2. the startup schedules ran
+/// 3. the first frame completed
+///
+/// Every plugin added with [`ProgressAppExt::add_timed_plugin`](crate::ProgressAppExt::add_timed_plugin)
+/// is tracked as another named task, and its build time is listed in [`PluginTimings`].
+/// Add this plugin before your other plugins, so the tracker can be filled while they initialize.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::StartupProgressPlugin;
+/// App::new().add_plugins(StartupProgressPlugin);
+/// ```
+pub struct StartupProgressPlugin;
+
+impl Plugin for StartupProgressPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ProgressPlugin::<AppStartup>::new())
+            .init_resource::<PluginTimings>()
+            .add_systems(PostStartup, startup_systems_done)
+            .add_systems(First, first_frame_done);
+        app.world_mut()
+            .resource_mut::<Progress<AppStartup>>()
+            .persist_tasks(3);
+    }
+}
+
+/// Resource with the build times of the plugins added with [`ProgressAppExt::add_timed_plugin`](crate::ProgressAppExt::add_timed_plugin)
+///
+/// The build time of a plugin includes the plugins it added itself.
+#[derive(Resource, Default, Debug)]
+pub struct PluginTimings {
+    plugins: Vec<(String, Duration)>,
+}
+
+impl PluginTimings {
+    /// Iterate over the names and build times of all timed plugins in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Duration)> {
+        self.plugins
+            .iter()
+            .map(|(name, duration)| (name.as_str(), *duration))
+    }
+
+    /// Returns the sum of the build times of all timed plugins
+    pub fn total(&self) -> Duration {
+        self.plugins.iter().map(|(_, duration)| *duration).sum()
+    }
+}
+
+/// Add the plugin and track its build as a named task of the [`AppStartup`] tracker
+pub(crate) fn add_timed_plugin<P: Plugin>(app: &mut App, plugin: P) {
+    if !app.is_plugin_added::<StartupProgressPlugin>() {
+        app.add_plugins(StartupProgressPlugin);
+    }
+    let name = plugin.name().to_owned();
+    let task = app
+        .world_mut()
+        .resource_mut::<Progress<AppStartup>>()
+        .add_named_task(name.clone());
+    let start = Instant::now();
+    app.add_plugins(plugin);
+    let duration = start.elapsed();
+    app.world_mut()
+        .resource_mut::<Progress<AppStartup>>()
+        .complete_named_task(task);
+    app.world_mut()
+        .resource_mut::<PluginTimings>()
+        .plugins
+        .push((name, duration));
+}
+
+fn startup_systems_done(mut progress: ResMut<Progress<AppStartup>>) {
+    progress.persist_done(1);
+}
+
+/// Marks the finished plugins in the first frame and the completed first frame in the second one
+fn first_frame_done(mut progress: ResMut<Progress<AppStartup>>, mut frames: Local<u8>) {
+    if *frames < 2 {
+        *frames += 1;
+        progress.persist_done(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, Plugin};
+
+    use crate::{
+        AppStartup, PluginTimings, Progress, ProgressAppExt, ProgressCount, StartupProgressPlugin,
+    };
+
+    #[test]
+    fn tracks_startup() {
+        let mut app = App::new();
+        app.add_plugins(StartupProgressPlugin);
+        app.finish();
+
+        app.update();
+        assert_eq!(
+            app.world().resource::<Progress<AppStartup>>().count(),
+            ProgressCount::new(2, 3)
+        );
+
+        app.update();
+        assert_eq!(
+            app.world().resource::<Progress<AppStartup>>().count(),
+            ProgressCount::done(3)
+        );
+    }
+
+    #[test]
+    fn tracks_timed_plugins_as_named_tasks() {
+        struct Physics;
+
+        impl Plugin for Physics {
+            fn build(&self, _app: &mut App) {}
+        }
+
+        let mut app = App::new();
+        app.add_timed_plugin(Physics);
+        app.finish();
+        app.update();
+
+        let progress = app.world().resource::<Progress<AppStartup>>();
+        let tasks: Vec<_> = progress
+            .named_tasks()
+            .map(|(_, label, done)| (label, done))
+            .collect();
+        let name =
+            "bevy_progress_tracking::startup::tests::tracks_timed_plugins_as_named_tasks::Physics";
+        assert_eq!(tasks, [(name, true)]);
+        assert_eq!(progress.count(), ProgressCount::new(3, 4));
+        let timings = app.world().resource::<PluginTimings>();
+        assert_eq!(
+            timings.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            [name]
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt::Write as _;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{fs, io};
+
+use bevy::app::{App, AppExit, Last, Plugin};
+use bevy::ecs::change_detection::DetectChanges;
+use bevy::ecs::event::EventReader;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Res, ResMut};
+use bevy::log::warn;
+use bevy::platform::time::Instant;
+
+use crate::file::write_atomic;
+use crate::{Objectives, ProgressAppExt, ProgressCount, ProgressMarker};
+
+/// Plugin storing the [`Objectives<T>`] in a file to keep long-term progress between runs
+///
+/// This is meant for progress like achievements or collection completion. The objectives are loaded
+/// from the file when the plugin is added and saved whenever they changed, at most once per autosave
+/// interval and a last time when the app exits. Loaded objectives that are already complete do not send
+/// another [`ObjectiveCompleted<T>`](crate::ObjectiveCompleted) event.
+/// Only available with the `persist_store` feature.
+/// ```edition2021,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{ProgressPlugin, ProgressStorePlugin};
+/// struct Achievements;
+///
+/// App::new().add_plugins((
+///     ProgressPlugin::<Achievements>::new(),
+///     ProgressStorePlugin::<Achievements>::new("achievements.txt"),
+/// ));
+/// ```
+pub struct ProgressStorePlugin<T> {
+    path: PathBuf,
+    autosave: Duration,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ProgressStorePlugin<T> {
+    /// Create a plugin storing the objectives in the file at the given path, saving at most every 5 seconds
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        ProgressStorePlugin {
+            path: path.into(),
+            autosave: Duration::from_secs(5),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the minimal time between two saves of changed objectives
+    pub fn with_autosave(mut self, autosave: Duration) -> Self {
+        self.autosave = autosave;
+        self
+    }
+}
+
+impl<T: ProgressMarker> Plugin for ProgressStorePlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.track_objectives::<T>();
+        let store = ProgressStore::<T> {
+            path: self.path.clone(),
+            autosave: self.autosave,
+            last_save: Instant::now(),
+            dirty: false,
+            _marker: PhantomData,
+        };
+        match store.load() {
+            Ok(entries) => {
+                let mut objectives = app.world_mut().resource_mut::<Objectives<T>>();
+                for (label, count) in entries {
+                    objectives.restore(label, count);
+                }
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => warn!(
+                "Failed to load progress from '{}': {}",
+                self.path.display(),
+                error
+            ),
+        }
+        app.insert_resource(store)
+            .add_systems(Last, autosave_objectives::<T>);
+    }
+}
+
+/// Resource of the file storing the [`Objectives<T>`]
+///
+/// Added by the [`ProgressStorePlugin`].
+#[derive(Resource)]
+pub struct ProgressStore<T> {
+    path: PathBuf,
+    autosave: Duration,
+    last_save: Instant,
+    dirty: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ProgressStore<T> {
+    /// Returns the path of the file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write the given objectives to the file
+    ///
+    /// Every objective is stored on its own line, so labels should not contain line breaks.
+    pub fn save(&mut self, objectives: &Objectives<T>) -> io::Result<()> {
+        let mut content = String::new();
+        for (label, count) in objectives.iter() {
+            let _ = writeln!(content, "{} {} {}", count.done, count.total, label);
+        }
+        write_atomic(&self.path, content)?;
+        self.last_save = Instant::now();
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn load(&self) -> io::Result<Vec<(String, ProgressCount)>> {
+        let content = fs::read_to_string(&self.path)?;
+        content
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                parse_entry(line).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid entry '{line}'"),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+fn parse_entry(line: &str) -> Option<(String, ProgressCount)> {
+    let mut parts = line.splitn(3, ' ');
+    let done = parts.next()?.parse().ok()?;
+    let total = parts.next()?.parse().ok()?;
+    let label = parts.next()?;
+    (done <= total).then(|| (label.to_owned(), ProgressCount::new(done, total)))
+}
+
+fn autosave_objectives<T: ProgressMarker>(
+    objectives: Res<Objectives<T>>,
+    mut store: ResMut<ProgressStore<T>>,
+    mut exit: EventReader<AppExit>,
+) {
+    store.dirty |= objectives.is_changed();
+    let exiting = exit.read().count() > 0;
+    if !store.dirty || (!exiting && store.last_save.elapsed() < store.autosave) {
+        return;
+    }
+    if let Err(error) = store.save(&objectives) {
+        warn!(
+            "Failed to save progress to '{}': {}",
+            store.path.display(),
+            error
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::Duration;
+
+    use bevy::app::App;
+
+    use crate::store::parse_entry;
+    use crate::{Objectives, Progress, ProgressCount, ProgressPlugin, ProgressStorePlugin};
+
+    struct Achievements;
+
+    #[test]
+    fn parses_labels_with_spaces() {
+        assert_eq!(
+            parse_entry("3 10 collect all gems"),
+            Some(("collect all gems".to_owned(), ProgressCount::new(3, 10)))
+        );
+        assert_eq!(parse_entry("3 2 broken"), None);
+        assert_eq!(parse_entry("3 gems"), None);
+    }
+
+    #[test]
+    fn restores_saved_objectives() {
+        let path = std::env::temp_dir().join("bevy_progress_tracking_store_test.txt");
+        fs::write(&path, "1 2 find secrets\n").unwrap();
+
+        let mut app = App::new();
+        app.add_plugins((
+            ProgressPlugin::<Achievements>::new(),
+            ProgressStorePlugin::<Achievements>::new(&path).with_autosave(Duration::ZERO),
+        ));
+        app.world_mut()
+            .resource_mut::<Objectives<Achievements>>()
+            .advance("find secrets", 1);
+        app.update();
+        app.update();
+        assert_eq!(
+            app.world().resource::<Progress<Achievements>>().count(),
+            ProgressCount::done(2)
+        );
+        let saved = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(saved, "2 2 find secrets\n");
+    }
+}
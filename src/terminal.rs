@@ -0,0 +1,122 @@
+use std::io::Write;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{App, Last, Plugin};
+use bevy::ecs::system::{Local, Res};
+use bevy::platform::time::Instant;
+
+use crate::{Progress, ProgressCount, ProgressMarker};
+
+const BAR_WIDTH: usize = 30;
+
+/// Plugin printing the progress of the tracker `T` as a bar to the terminal
+///
+/// This is meant for headless runs like dedicated servers or asset bakes on CI.
+/// The bar is redrawn at most once per interval and a last time when all tasks are done.
+/// Only available with the `terminal` feature.
+/// ```edition2021
+/// # use std::time::Duration;
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{ProgressPlugin, TerminalProgressPlugin};
+/// # struct WorldGeneration;
+/// App::new().add_plugins((
+///     ProgressPlugin::<WorldGeneration>::new(),
+///     TerminalProgressPlugin::<WorldGeneration>::new().with_interval(Duration::from_secs(1)),
+/// ));
+/// ```
+pub struct TerminalProgressPlugin<T> {
+    interval: Duration,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> TerminalProgressPlugin<T> {
+    /// Create a plugin redrawing the bar every 250 milliseconds
+    pub fn new() -> Self {
+        TerminalProgressPlugin {
+            interval: Duration::from_millis(250),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the minimal time between two redraws of the bar
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+impl<T> Default for TerminalProgressPlugin<T> {
+    fn default() -> Self {
+        TerminalProgressPlugin::new()
+    }
+}
+
+impl<T: ProgressMarker> Plugin for TerminalProgressPlugin<T> {
+    fn build(&self, app: &mut App) {
+        let interval = self.interval;
+        app.add_systems(
+            Last,
+            move |progress: Res<Progress<T>>, mut state: Local<TerminalState>| {
+                state.draw(progress.count(), interval);
+            },
+        );
+    }
+}
+
+#[derive(Default)]
+struct TerminalState {
+    last_draw: Option<Instant>,
+    finished: bool,
+}
+
+impl TerminalState {
+    fn draw(&mut self, count: ProgressCount, interval: Duration) {
+        let complete = count.total > 0 && count.is_complete();
+        if !complete {
+            self.finished = false;
+        } else if self.finished {
+            return;
+        }
+        let due = self
+            .last_draw
+            .is_none_or(|last_draw| last_draw.elapsed() >= interval);
+        if !due && !complete {
+            return;
+        }
+        self.last_draw = Some(Instant::now());
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(stderr, "\r{}", render_bar(count));
+        if complete {
+            self.finished = true;
+            let _ = writeln!(stderr);
+        }
+        let _ = stderr.flush();
+    }
+}
+
+fn render_bar(count: ProgressCount) -> String {
+    let filled = count.weighted(BAR_WIDTH).done;
+    format!(
+        "[{}{}] {:>3}% ({}/{})",
+        "#".repeat(filled),
+        "-".repeat(BAR_WIDTH - filled),
+        count.per_mille() / 10,
+        count.done,
+        count.total
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::terminal::render_bar;
+    use crate::ProgressCount;
+
+    #[test]
+    fn renders_bar() {
+        assert_eq!(
+            render_bar(ProgressCount::new(1, 3)),
+            "[##########--------------------]  33% (1/3)"
+        );
+    }
+}
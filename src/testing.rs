@@ -0,0 +1,28 @@
+use bevy::app::App;
+use bevy::ecs::component::Component;
+use bevy::ecs::resource::Resource;
+
+use crate::{Progress, ProgressCount, ProgressPlugin};
+
+/// Marker of the tracker added by [`app`]
+pub(crate) struct Loading;
+
+/// Component of entities with pending work
+#[derive(Component)]
+pub(crate) struct Pending;
+
+/// Resource toggling tracked work between pending and done
+#[derive(Resource)]
+pub(crate) struct Ready(pub(crate) bool);
+
+/// Returns an app with a [`ProgressPlugin`] for the [`Loading`] tracker
+pub(crate) fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(ProgressPlugin::<Loading>::new());
+    app
+}
+
+/// Returns the count of the last finished frame of the [`Loading`] tracker
+pub(crate) fn count(app: &App) -> ProgressCount {
+    app.world().resource::<Progress<Loading>>().count()
+}
@@ -0,0 +1,192 @@
+use bevy::asset::{AssetServer, Assets, Handle};
+use bevy::color::Color;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::system::{Res, ResMut, SystemState};
+use bevy::ecs::world::World;
+use bevy::image::{Image, TextureAtlasLayout};
+use bevy::log::warn;
+use bevy::text::{
+    ComputedTextBlock, CosmicFontSystem, Font, FontAtlasSets, SwashCache, TextBounds, TextFont,
+    TextLayout, TextLayoutInfo, TextPipeline, YAxisOrientation,
+};
+
+use crate::WarmUp;
+
+type GlyphResources<'w> = (
+    ResMut<'w, TextPipeline>,
+    Res<'w, Assets<Font>>,
+    ResMut<'w, FontAtlasSets>,
+    ResMut<'w, Assets<TextureAtlasLayout>>,
+    ResMut<'w, Assets<Image>>,
+    ResMut<'w, CosmicFontSystem>,
+    ResMut<'w, SwashCache>,
+);
+
+/// [`WarmUp`] rasterizing a set of glyphs of a font into the font atlases
+///
+/// Without it, the glyphs of the first text heavy screen are rasterized in the frame it is shown,
+/// which can hitch right after the loading screen reported to be done.
+/// The glyphs are rasterized once the font is loaded, at every configured font size. Text using
+/// the font at one of these sizes and the same scale factor then finds its glyphs in the atlases.
+/// If the font fails to load, or the `TextPlugin` is missing, the warm-up is skipped with a warning.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{GlyphWarmUp, ProgressAppExt, ProgressPlugin};
+/// # struct Loading;
+/// # let font: Handle<Font> = Handle::default();
+/// App::new()
+///     .add_plugins(ProgressPlugin::<Loading>::new())
+///     .register_warm_up::<Loading, _>(
+///         GlyphWarmUp::new(font, "0123456789 abcdefghijklmnopqrstuvwxyz")
+///             .with_font_size(16.)
+///             .with_font_size(32.),
+///     );
+/// ```
+pub struct GlyphWarmUp {
+    font: Handle<Font>,
+    glyphs: String,
+    font_sizes: Vec<f32>,
+    scale_factor: f32,
+    warm: bool,
+}
+
+impl GlyphWarmUp {
+    /// Warm up the given glyphs of the font
+    ///
+    /// Without any [font sizes](Self::with_font_size), the default size of [`TextFont`] is used.
+    pub fn new(font: Handle<Font>, glyphs: impl Into<String>) -> Self {
+        Self {
+            font,
+            glyphs: glyphs.into(),
+            font_sizes: Vec::new(),
+            scale_factor: 1.,
+            warm: false,
+        }
+    }
+
+    /// Also rasterize the glyphs at the given font size
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_sizes.push(font_size);
+        self
+    }
+
+    /// Rasterize the glyphs for the given scale factor of the window instead of `1`
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    fn rasterize(&self, world: &mut World) -> bool {
+        let mut state = SystemState::<GlyphResources>::new(world);
+        let (
+            mut pipeline,
+            fonts,
+            mut font_atlas_sets,
+            mut texture_atlases,
+            mut textures,
+            mut font_system,
+            mut swash_cache,
+        ) = state.get_mut(world);
+        if !fonts.contains(&self.font) {
+            return false;
+        }
+        let default_size = [TextFont::default().font_size];
+        let font_sizes = if self.font_sizes.is_empty() {
+            &default_size[..]
+        } else {
+            &self.font_sizes
+        };
+        for &font_size in font_sizes {
+            let font = TextFont {
+                font: self.font.clone(),
+                font_size,
+                ..TextFont::default()
+            };
+            let span = (
+                Entity::PLACEHOLDER,
+                0,
+                self.glyphs.as_str(),
+                &font,
+                Color::WHITE,
+            );
+            if let Err(error) = pipeline.queue_text(
+                &mut TextLayoutInfo::default(),
+                &fonts,
+                [span].into_iter(),
+                self.scale_factor as f64,
+                &TextLayout::default(),
+                TextBounds::UNBOUNDED,
+                &mut font_atlas_sets,
+                &mut texture_atlases,
+                &mut textures,
+                YAxisOrientation::TopToBottom,
+                &mut ComputedTextBlock::default(),
+                &mut font_system,
+                &mut swash_cache,
+            ) {
+                warn!("Failed to warm up glyphs at font size {font_size}: {error}");
+            }
+        }
+        true
+    }
+}
+
+impl WarmUp for GlyphWarmUp {
+    fn is_warm(&mut self, _world: &World) -> bool {
+        self.warm
+    }
+
+    fn warm_up(&mut self, world: &mut World) {
+        if !world.contains_resource::<TextPipeline>() {
+            warn!("Add the TextPlugin to warm up glyphs");
+            self.warm = true;
+        } else if world
+            .get_resource::<AssetServer>()
+            .is_some_and(|server| server.load_state(&self.font).is_failed())
+        {
+            warn!("Failed to load the font to warm up glyphs");
+            self.warm = true;
+        } else {
+            self.warm = self.rasterize(world);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::App;
+    use bevy::asset::{AssetApp, AssetPlugin, Assets, Handle};
+    use bevy::image::{Image, TextureAtlasLayout};
+    use bevy::text::{CosmicFontSystem, Font, FontAtlasSets, SwashCache, TextPipeline};
+
+    use crate::testing::{self, Loading};
+    use crate::{GlyphWarmUp, Progress, ProgressAppExt, ProgressPlugin};
+
+    #[test]
+    fn waits_for_the_font() {
+        let mut app = App::new();
+        app.add_plugins((AssetPlugin::default(), ProgressPlugin::<Loading>::new()))
+            .init_asset::<Font>()
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_resource::<TextPipeline>()
+            .init_resource::<FontAtlasSets>()
+            .init_resource::<CosmicFontSystem>()
+            .init_resource::<SwashCache>();
+        let font: Handle<Font> = app.world().resource::<Assets<Font>>().reserve_handle();
+        app.register_warm_up::<Loading, _>(GlyphWarmUp::new(font, "abc"));
+
+        app.update();
+        app.update();
+        assert!(!app.world().resource::<Progress<Loading>>().is_ready());
+    }
+
+    #[test]
+    fn skips_without_text_plugin() {
+        let mut app = testing::app();
+        app.register_warm_up::<Loading, _>(GlyphWarmUp::new(Handle::default(), "abc"));
+
+        app.update();
+        assert!(app.world().resource::<Progress<Loading>>().is_ready());
+    }
+}
@@ -0,0 +1,75 @@
+use bevy::ecs::system::{Commands, Res, SystemId};
+
+use crate::{Progress, ProgressMarker};
+
+/// Returns a system running the given system every time the progress of the tracker `T` reaches the threshold
+pub(crate) fn run_at_progress<T: ProgressMarker>(
+    threshold: f32,
+    system: SystemId,
+) -> impl FnMut(Commands, Res<Progress<T>>) {
+    let mut reached = false;
+    move |mut commands, progress| {
+        if progress.count().total == 0 {
+            return;
+        }
+        let above = progress.progress() >= threshold;
+        if above && !reached {
+            commands.run_system(system);
+        }
+        reached = above;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, Update};
+    use bevy::ecs::resource::Resource;
+    use bevy::ecs::schedule::ScheduleLabel;
+    use bevy::ecs::system::{Res, ResMut};
+
+    use crate::testing::{self, Loading, Ready};
+    use crate::{Progress, ProgressAppExt, ProgressPlugin};
+
+    #[derive(Resource, Default)]
+    struct Runs(Vec<&'static str>);
+
+    fn track_ready(ready: Res<Ready>, mut progress: ResMut<Progress<Loading>>) {
+        progress.track(2, 1 + ready.0 as usize);
+    }
+
+    #[test]
+    fn runs_systems_at_progress_thresholds() {
+        let mut app = testing::app();
+        app.init_resource::<Runs>()
+            .insert_resource(Ready(false))
+            .add_systems(Update, track_ready)
+            .run_at_progress::<Loading, _>(0.5, |mut runs: ResMut<Runs>| runs.0.push("half"))
+            .run_at_progress::<Loading, _>(1., |mut runs: ResMut<Runs>| runs.0.push("done"));
+
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<Runs>().0, ["half"]);
+        app.world_mut().resource_mut::<Ready>().0 = true;
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<Runs>().0, ["half", "done"]);
+    }
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct FinishLoading;
+
+    #[test]
+    fn runs_in_the_finish_schedule_of_later_added_trackers() {
+        let mut app = App::new();
+        app.init_resource::<Runs>()
+            .insert_resource(Ready(true))
+            .add_systems(Update, track_ready)
+            .run_at_progress::<Loading, _>(1., |mut runs: ResMut<Runs>| runs.0.push("done"))
+            .add_plugins(ProgressPlugin::<Loading>::new().finish_frame_in(FinishLoading));
+
+        app.update();
+        assert!(app.world().resource::<Runs>().0.is_empty());
+        app.world_mut().run_schedule(FinishLoading);
+        assert_eq!(app.world().resource::<Runs>().0, ["done"]);
+    }
+}
@@ -0,0 +1,137 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Res, ResMut};
+
+use crate::{Progress, ProgressClock, ProgressMarker};
+
+/// Resource rotating through loading screen tips while the tracker `T` is not complete
+///
+/// The current tip changes once per interval, based on the [`ProgressClock`].
+/// Rotation stops as soon as all tasks of the tracker are done, so the last tip stays visible
+/// until the loading screen is gone. Tips added with [`LoadingTips::with_long_tip`] are only shown
+/// while the [ETA](Progress::eta) of the tracker is long enough to read them. Add it with [`ProgressAppExt::rotate_tips`](crate::ProgressAppExt::rotate_tips)
+/// and display [`LoadingTips::current`] in your UI.
+/// ```edition2021
+/// # use std::time::Duration;
+/// # use bevy_progress_tracking::LoadingTips;
+/// # struct Loading;
+/// let tips = LoadingTips::<Loading>::new(["Press F to pay respects", "Dragons fear water"])
+///     .with_long_tip("The history of the realm spans ten ages...", Duration::from_secs(30))
+///     .with_interval(Duration::from_secs(8));
+/// assert_eq!(tips.current(), Some("Press F to pay respects"));
+/// ```
+#[derive(Resource)]
+pub struct LoadingTips<T> {
+    tips: Vec<(Cow<'static, str>, Duration)>,
+    interval: Duration,
+    current: usize,
+    shown_for: Duration,
+    eta: Option<Duration>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> LoadingTips<T> {
+    /// Create tips changing every 5 seconds
+    pub fn new<S: Into<Cow<'static, str>>>(tips: impl IntoIterator<Item = S>) -> Self {
+        LoadingTips {
+            tips: tips
+                .into_iter()
+                .map(|tip| (tip.into(), Duration::ZERO))
+                .collect(),
+            interval: Duration::from_secs(5),
+            current: 0,
+            shown_for: Duration::ZERO,
+            eta: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the time every tip is shown for
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Add a tip that is only shown while loading is estimated to take at least `min_eta`
+    ///
+    /// Use this for tips that take a while to read, so short loads do not cut them off.
+    pub fn with_long_tip(mut self, tip: impl Into<Cow<'static, str>>, min_eta: Duration) -> Self {
+        self.tips.push((tip.into(), min_eta));
+        self
+    }
+
+    /// Returns the tip that should be shown
+    pub fn current(&self) -> Option<&str> {
+        self.tips.get(self.current).map(|(tip, _)| tip.as_ref())
+    }
+
+    /// Show the next tip that fits the last known ETA of the tracker
+    ///
+    /// Without an ETA, only tips without a minimum ETA are shown.
+    pub fn next(&mut self) {
+        self.shown_for = Duration::ZERO;
+        let eta = self.eta.unwrap_or(Duration::ZERO);
+        let fits = |offset: usize| {
+            let index = (self.current + offset) % self.tips.len();
+            (self.tips[index].1 <= eta).then_some(index)
+        };
+        if let Some(index) = (1..=self.tips.len()).find_map(fits) {
+            self.current = index;
+        }
+    }
+
+    fn advance(&mut self, delta: Duration, eta: Option<Duration>) {
+        self.eta = eta;
+        self.shown_for += delta;
+        if self.shown_for >= self.interval {
+            self.next();
+        }
+    }
+}
+
+pub(crate) fn rotate_tips<T: ProgressMarker>(
+    progress: Res<Progress<T>>,
+    mut tips: ResMut<LoadingTips<T>>,
+    clock: Res<ProgressClock>,
+) {
+    if !progress.count().is_complete() {
+        tips.advance(clock.delta(), progress.eta());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::LoadingTips;
+
+    struct Loading;
+
+    #[test]
+    fn rotates_after_interval() {
+        let mut tips =
+            LoadingTips::<Loading>::new(["first", "second"]).with_interval(Duration::from_secs(2));
+        tips.advance(Duration::from_secs(1), None);
+        assert_eq!(tips.current(), Some("first"));
+        tips.advance(Duration::from_secs(1), None);
+        assert_eq!(tips.current(), Some("second"));
+        tips.advance(Duration::from_secs(2), None);
+        assert_eq!(tips.current(), Some("first"));
+    }
+
+    #[test]
+    fn shows_long_tips_for_long_etas() {
+        let mut tips = LoadingTips::<Loading>::new(["short"])
+            .with_long_tip("long", Duration::from_secs(30))
+            .with_interval(Duration::from_secs(2));
+        tips.advance(Duration::from_secs(2), Some(Duration::from_secs(10)));
+        assert_eq!(tips.current(), Some("short"));
+        tips.advance(Duration::from_secs(2), Some(Duration::from_secs(40)));
+        assert_eq!(tips.current(), Some("long"));
+        tips.advance(Duration::from_secs(2), None);
+        assert_eq!(tips.current(), Some("short"));
+    }
+}
@@ -0,0 +1,87 @@
+use bevy::ecs::system::{Res, SystemParam};
+
+use crate::{Progress, ProgressCount, ProgressMarker};
+
+/// Read-only access to the evaluated progress of the tracker `T`
+///
+/// All values are taken from the last finished frame.
+/// Systems only reading progress, like UI systems, can use this instead of [`Progress`] to make sure
+/// they do not change any counts and can run in parallel with each other
+/// and with systems tracking progress through [`SharedProgress`](crate::SharedProgress).
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::ProgressView;
+/// # struct Loading;
+/// fn print_progress(progress: ProgressView<Loading>) {
+///     println!("Loading {:.0}%", progress.progress() * 100.);
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct ProgressView<'w, T: ProgressMarker> {
+    progress: Res<'w, Progress<T>>,
+}
+
+impl<T: ProgressMarker> ProgressView<'_, T> {
+    /// Returns the progress as a floating point number between 0 and 1
+    pub fn progress(&self) -> f32 {
+        self.progress.progress()
+    }
+
+    /// Returns the progress to display in loading screens
+    ///
+    /// See [`Progress::displayed_progress`].
+    pub fn displayed_progress(&self) -> f32 {
+        self.progress.displayed_progress()
+    }
+
+    /// Returns the done and total tasks
+    pub fn count(&self) -> ProgressCount {
+        self.progress.count()
+    }
+
+    /// Returns `true` if all tasks are done
+    pub fn is_complete(&self) -> bool {
+        self.progress.count().is_complete()
+    }
+
+    /// Returns `true` if all hard tasks are done
+    ///
+    /// See [`Progress::is_ready`].
+    pub fn is_ready(&self) -> bool {
+        self.progress.is_ready()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, Update};
+    use bevy::ecs::resource::Resource;
+    use bevy::ecs::system::ResMut;
+
+    use crate::{Progress, ProgressPlugin, ProgressView};
+
+    struct Loading;
+
+    #[derive(Resource, Default)]
+    struct Complete(bool);
+
+    #[test]
+    fn reads_last_frame() {
+        let mut app = App::new();
+        app.add_plugins(ProgressPlugin::<Loading>::new())
+            .init_resource::<Complete>()
+            .add_systems(
+                Update,
+                |view: ProgressView<Loading>, mut complete: ResMut<Complete>| {
+                    complete.0 = view.is_complete();
+                },
+            );
+        app.world_mut()
+            .resource_mut::<Progress<Loading>>()
+            .persist_tasks(1);
+
+        app.update();
+        app.update();
+        assert!(!app.world().resource::<Complete>().0);
+    }
+}
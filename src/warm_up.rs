@@ -0,0 +1,231 @@
+use std::time::Duration;
+
+use bevy::ecs::system::{Res, ResMut};
+use bevy::ecs::world::World;
+use bevy::time::{Real, Time};
+
+use crate::{Progress, ProgressCount, ProgressMarker};
+
+/// Trait for plugins to report a warm-up step, like building navigation meshes or settling physics
+///
+/// Warm-up steps are registered with [`ProgressAppExt::register_warm_up`](crate::ProgressAppExt::register_warm_up)
+/// and count as one [hidden task](crate::Progress::track_hidden) each. They keep the tracker from being
+/// [ready](crate::Progress::is_ready), but do not show up on the loading bar.
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin, WarmUp};
+/// #[derive(Resource)]
+/// struct NavMesh {
+///     tiles_left: usize,
+/// }
+///
+/// struct NavMeshWarmUp;
+///
+/// impl WarmUp for NavMeshWarmUp {
+///     fn is_warm(&mut self, world: &World) -> bool {
+///         world
+///             .get_resource::<NavMesh>()
+///             .is_some_and(|nav_mesh| nav_mesh.tiles_left == 0)
+///     }
+/// }
+///
+/// # struct Loading;
+/// App::new()
+///     .add_plugins(ProgressPlugin::<Loading>::new())
+///     .register_warm_up::<Loading, _>(NavMeshWarmUp);
+/// ```
+pub trait WarmUp: Send + Sync + 'static {
+    /// Returns `true` once the warm-up is complete
+    ///
+    /// This is called once per frame until it returns `true` for the first time.
+    fn is_warm(&mut self, world: &World) -> bool;
+
+    /// Advance the warm-up with exclusive access to the world
+    ///
+    /// This is called once per frame before [`is_warm`](WarmUp::is_warm) until the warm-up is complete.
+    /// The default implementation does nothing, for warm-ups that only observe the world.
+    fn warm_up(&mut self, _world: &mut World) {}
+}
+
+/// Returns a system tracking the warm-up as a hidden task of the tracker `T`
+pub(crate) fn track_warm_up<T: ProgressMarker, W: WarmUp>(
+    mut warm_up: W,
+) -> impl FnMut(&mut World) {
+    let mut done = false;
+    move |world| {
+        if !done {
+            warm_up.warm_up(world);
+            done = warm_up.is_warm(world);
+        }
+        world
+            .resource_mut::<Progress<T>>()
+            .track_hidden(1, done as usize);
+    }
+}
+
+/// Returns a system tracking a hidden task that is done once the tracker `T` was loaded for the given number of fast frames
+pub(crate) fn defer_completion_until_stable<T: ProgressMarker>(
+    max_frame_time: Duration,
+    frames: u32,
+) -> impl FnMut(ResMut<Progress<T>>, Res<Time<Real>>) {
+    let mut stable_frames = 0;
+    let mut reported: Option<bool> = None;
+    let mut last_counts = None;
+    move |mut progress, time| {
+        // The hidden count of the last frame includes this task
+        let hidden = progress.hidden_count();
+        let own = reported.map_or(ProgressCount::default(), ProgressCount::from);
+        let others = ProgressCount::new(
+            hidden.done.saturating_sub(own.done),
+            hidden.total.saturating_sub(own.total),
+        );
+        let counts = (progress.hard_count(), others);
+        if last_counts != Some(counts) {
+            last_counts = Some(counts);
+            stable_frames = 0;
+        }
+        if !counts.0.is_complete() || !counts.1.is_complete() {
+            stable_frames = 0;
+        } else if stable_frames < frames {
+            stable_frames = if time.delta() < max_frame_time {
+                stable_frames + 1
+            } else {
+                0
+            };
+        }
+        let done = stable_frames >= frames;
+        reported = Some(done);
+        progress.track_hidden(1, done as usize);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::app::Update;
+    use bevy::ecs::system::{Res, ResMut};
+    use bevy::ecs::world::World;
+    use bevy::time::{TimePlugin, TimeUpdateStrategy};
+
+    use crate::testing::{self, Loading, Ready};
+    use crate::{Progress, ProgressAppExt, ProgressCount, WarmUp};
+
+    struct Frames(usize);
+
+    impl WarmUp for Frames {
+        fn is_warm(&mut self, _world: &World) -> bool {
+            self.0 = self.0.saturating_sub(1);
+            self.0 == 0
+        }
+    }
+
+    #[test]
+    fn warm_up_gates_readiness() {
+        let mut app = testing::app();
+        app.register_warm_up::<Loading, _>(Frames(2));
+
+        app.update();
+        let progress = app.world().resource::<Progress<Loading>>();
+        assert!(!progress.is_ready());
+        assert_eq!(progress.count(), ProgressCount::default());
+
+        app.update();
+        let progress = app.world().resource::<Progress<Loading>>();
+        assert!(progress.is_ready());
+        assert_eq!(progress.hidden_count(), ProgressCount::done(1));
+    }
+
+    #[test]
+    fn warm_up_advances_before_checking() {
+        struct Steps(usize);
+
+        impl WarmUp for Steps {
+            fn is_warm(&mut self, _world: &World) -> bool {
+                self.0 == 2
+            }
+
+            fn warm_up(&mut self, _world: &mut World) {
+                self.0 += 1;
+            }
+        }
+
+        let mut app = testing::app();
+        app.register_warm_up::<Loading, _>(Steps(0));
+
+        app.update();
+        assert!(!app.world().resource::<Progress<Loading>>().is_ready());
+        app.update();
+        assert!(app.world().resource::<Progress<Loading>>().is_ready());
+    }
+
+    #[test]
+    fn defers_readiness_until_frames_are_stable() {
+        let mut app = testing::app();
+        app.add_plugins(TimePlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                50,
+            )))
+            .defer_completion_until_stable::<Loading>(Duration::from_millis(20), 2)
+            .insert_resource(Ready(true))
+            .add_systems(
+                Update,
+                |ready: Res<Ready>, mut progress: ResMut<Progress<Loading>>| {
+                    progress.track(1, ready.0 as usize)
+                },
+            );
+
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(!app.world().resource::<Progress<Loading>>().is_ready());
+
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            10,
+        )));
+        app.update();
+        assert!(!app.world().resource::<Progress<Loading>>().is_ready());
+        app.update();
+        assert!(app.world().resource::<Progress<Loading>>().is_ready());
+
+        app.world_mut().resource_mut::<Ready>().0 = false;
+        app.update();
+        app.update();
+        app.world_mut().resource_mut::<Ready>().0 = true;
+        app.update();
+        app.update();
+        assert!(!app.world().resource::<Progress<Loading>>().is_ready());
+        app.update();
+        assert!(app.world().resource::<Progress<Loading>>().is_ready());
+    }
+
+    #[test]
+    fn restarts_stable_frames_when_warm_ups_are_pending_again() {
+        let mut app = testing::app();
+        app.add_plugins(TimePlugin)
+            .defer_completion_until_stable::<Loading>(Duration::from_millis(20), 2)
+            .insert_resource(Ready(true))
+            .add_systems(
+                Update,
+                |ready: Res<Ready>, mut progress: ResMut<Progress<Loading>>| {
+                    progress.track(1, 1);
+                    progress.track_hidden(1, ready.0 as usize);
+                },
+            );
+
+        for _ in 0..4 {
+            app.update();
+        }
+        assert!(app.world().resource::<Progress<Loading>>().is_ready());
+
+        app.world_mut().resource_mut::<Ready>().0 = false;
+        app.update();
+        app.update();
+        app.world_mut().resource_mut::<Ready>().0 = true;
+        app.update();
+        assert!(!app.world().resource::<Progress<Loading>>().is_ready());
+        app.update();
+        app.update();
+        assert!(app.world().resource::<Progress<Loading>>().is_ready());
+    }
+}
@@ -0,0 +1,47 @@
+use std::any::type_name;
+
+use bevy::ecs::system::{Local, Res};
+use web_sys::js_sys::{Object, Reflect};
+use web_sys::wasm_bindgen::JsValue;
+use web_sys::{CustomEvent, CustomEventInit};
+
+use crate::{Progress, ProgressCount, ProgressMarker};
+
+/// Name of the custom event dispatched on the window whenever the progress of a tracker changes
+pub const PROGRESS_EVENT: &str = "bevy-progress";
+
+/// Mirror the progress of the tracker `T` to the hosting page
+///
+/// A [`PROGRESS_EVENT`] custom event is dispatched on the window and a message is posted to the parent window
+/// if the app runs in an iframe. Both carry an object with the `tracker` type name, the `done` and `total` tasks
+/// and the `progress` between 0 and 1.
+pub(crate) fn post_progress<T: ProgressMarker>(
+    progress: Res<Progress<T>>,
+    mut last: Local<Option<ProgressCount>>,
+) {
+    let count = progress.count();
+    if *last == Some(count) {
+        return;
+    }
+    *last = Some(count);
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    let detail = Object::new();
+    let _ = Reflect::set(&detail, &"tracker".into(), &type_name::<T>().into());
+    let _ = Reflect::set(&detail, &"done".into(), &JsValue::from(count.done as f64));
+    let _ = Reflect::set(&detail, &"total".into(), &JsValue::from(count.total as f64));
+    let _ = Reflect::set(&detail, &"progress".into(), &JsValue::from(count.ratio()));
+
+    let init = CustomEventInit::new();
+    init.set_detail(&detail);
+    if let Ok(event) = CustomEvent::new_with_event_init_dict(PROGRESS_EVENT, &init) {
+        let _ = window.dispatch_event(&event);
+    }
+    if let Ok(Some(parent)) = window.parent() {
+        if parent != window {
+            let _ = parent.post_message(&detail, "*");
+        }
+    }
+}
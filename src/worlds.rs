@@ -0,0 +1,130 @@
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::Res;
+use bevy::ecs::world::WorldId;
+use bevy::platform::collections::HashMap;
+
+use crate::{Progress, ProgressCount, ProgressMarker};
+
+/// Progress of the tracker `T` in multiple worlds
+///
+/// Every world has its own [`Progress`] resources, so apps with multiple worlds, like a server and a client
+/// in the same process, can use the same marker types. To aggregate their progress,
+/// clone this resource into every app with [`ProgressAppExt::share_progress_across_worlds`](crate::ProgressAppExt::share_progress_across_worlds).
+/// Each world then publishes the counts of its last finished frame keyed by its [`WorldId`].
+/// ```edition2021
+/// # use bevy::prelude::*;
+/// # use bevy_progress_tracking::{ProgressAppExt, ProgressPlugin, WorldsProgress};
+/// # struct Loading;
+/// let worlds = WorldsProgress::<Loading>::default();
+/// let mut server = App::new();
+/// server
+///     .add_plugins(ProgressPlugin::<Loading>::new())
+///     .share_progress_across_worlds(worlds.clone());
+/// let mut client = App::new();
+/// client
+///     .add_plugins(ProgressPlugin::<Loading>::new())
+///     .share_progress_across_worlds(worlds.clone());
+///
+/// server.update();
+/// client.update();
+/// assert!(worlds.aggregate().is_complete());
+/// ```
+#[derive(Resource)]
+pub struct WorldsProgress<T> {
+    counts: Arc<Mutex<HashMap<WorldId, ProgressCount>>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for WorldsProgress<T> {
+    fn default() -> Self {
+        WorldsProgress {
+            counts: Arc::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for WorldsProgress<T> {
+    fn clone(&self) -> Self {
+        WorldsProgress {
+            counts: self.counts.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> WorldsProgress<T> {
+    /// Returns the published counts of all worlds
+    pub fn counts(&self) -> Vec<(WorldId, ProgressCount)> {
+        self.lock()
+            .iter()
+            .map(|(world, count)| (*world, *count))
+            .collect()
+    }
+
+    /// Returns the published counts of the given world
+    pub fn get(&self, world: WorldId) -> Option<ProgressCount> {
+        self.lock().get(&world).copied()
+    }
+
+    /// Returns the sum of the published counts of all worlds
+    pub fn aggregate(&self) -> ProgressCount {
+        self.lock().values().sum()
+    }
+
+    /// Stop aggregating the progress of the given world
+    pub fn remove(&self, world: WorldId) {
+        self.lock().remove(&world);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<WorldId, ProgressCount>> {
+        self.counts
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+pub(crate) fn publish_world_progress<T: ProgressMarker>(
+    world: WorldId,
+    progress: Res<Progress<T>>,
+    worlds: Res<WorldsProgress<T>>,
+) {
+    worlds.lock().insert(world, progress.count());
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::App;
+
+    use crate::{Progress, ProgressAppExt, ProgressCount, ProgressPlugin, WorldsProgress};
+
+    struct Loading;
+
+    #[test]
+    fn aggregates_worlds() {
+        let worlds = WorldsProgress::<Loading>::default();
+        let mut apps: Vec<App> = (0..2)
+            .map(|tasks| {
+                let mut app = App::new();
+                app.add_plugins(ProgressPlugin::<Loading>::new())
+                    .share_progress_across_worlds(worlds.clone());
+                app.world_mut()
+                    .resource_mut::<Progress<Loading>>()
+                    .persist_tasks(tasks + 1);
+                app
+            })
+            .collect();
+
+        for _ in 0..2 {
+            apps.iter_mut().for_each(App::update);
+        }
+        assert_eq!(worlds.aggregate(), ProgressCount::pending(3));
+        assert_eq!(
+            worlds.get(apps[1].world().id()),
+            Some(ProgressCount::pending(2))
+        );
+    }
+}